@@ -1,8 +1,14 @@
 // toml crate for parsing config.toml
 use toml;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use std::fs;
+use std::{
+    fs,
+    path::PathBuf,
+    collections::BTreeMap
+};
+
+use nix::sys::signal::Signal;
 
 // internal imports
 use crate::data::*;
@@ -14,7 +20,82 @@ use crate::data::*;
 pub struct Config { // Structure of the TOML config file, fields are optional so you can leave them out and only specify the settings you want to change
     pub layout: Option<Layout>,
     pub window: Option<Window>,
-    pub feature: Option<Feature>
+    pub feature: Option<Feature>,
+    pub launch: Option<Launch>,
+    pub record: Option<Record>,
+    pub signals: Option<BTreeMap<String, Disposition>> // "SIGUSR1" = "pass", the signals left out stop the tracee
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Launch { // how the tracee gets started, the defaults for every executable (the Control pane edits the LAUNCH of the loaded one)
+    pub args: Option<Vec<String>>,
+    pub env: Option<Vec<String>>, // "KEY=VALUE" entries
+    pub clear_env: Option<bool>,
+    pub directory: Option<String>,
+    pub follow_fork: Option<FollowFork>,
+    pub stop_at: Option<StopAt>,
+    pub no_aslr: Option<bool> // the same addresses on every run
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
+pub enum FollowFork { // which process we keep tracing after the tracee forks
+    parent,
+    child,
+    both
+}
+
+impl std::fmt::Display for FollowFork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            FollowFork::parent => "Parent",
+            FollowFork::child => "Child",
+            FollowFork::both => "Both"
+        })
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
+pub enum StopAt { // where the tracee stops on its own after the launch
+    none,
+    entry, // the ELF entry point, before any of the program's code runs
+    main // after the prologue of main
+}
+
+impl std::fmt::Display for StopAt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            StopAt::none => "None",
+            StopAt::entry => "Entry",
+            StopAt::main => "Main"
+        })
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
+pub enum Disposition { // what happens when the tracee gets a signal (edited in the Control pane, saved into the user config)
+    stop, // the tracee stops, the signal is delivered on the next continue
+    print, // logged in the Control pane and delivered, without stopping
+    pass, // delivered without stopping
+    ignore // never reaches the tracee
+}
+
+impl std::fmt::Display for Disposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Disposition::stop => "Stop",
+            Disposition::print => "Print",
+            Disposition::pass => "Pass",
+            Disposition::ignore => "Ignore"
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Record { // the reverse execution
+    pub history: Option<usize> // how many steps are kept, each holds the registers and the overwritten memory
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -85,7 +166,7 @@ impl Theme {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Layout {
     pub status_bar: Option<bool>,
     pub sidebar_left: Option<bool>,
@@ -96,7 +177,7 @@ pub struct Layout {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Deserialize, Debug, Clone, PartialEq, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
 pub enum PanelMode {
     middle,
     left,
@@ -104,7 +185,7 @@ pub enum PanelMode {
     full
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Panes {
     pub main: Vec<Pane>,
     pub left: Vec<Pane>,
@@ -113,7 +194,7 @@ pub struct Panes {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum Pane {
     memory,
     stack,
@@ -122,7 +203,12 @@ pub enum Pane {
     registers,
     info,
     control,
-    terminal
+    terminal,
+    threads,
+    syscalls,
+    watch,
+    log,
+    breakpoints
 }
 
 impl Default for Config {
@@ -146,6 +232,35 @@ impl Config {
             }
         }
 
+        match &mut self.launch {
+            None => self.launch = default.launch,
+            Some(launch) => {
+                let default = default.launch.unwrap();
+                if launch.args.is_none() {launch.args = default.args;}
+                if launch.env.is_none() {launch.env = default.env;}
+                if launch.clear_env.is_none() {launch.clear_env = default.clear_env;}
+                if launch.directory.is_none() {launch.directory = default.directory;}
+                if launch.follow_fork.is_none() {launch.follow_fork = default.follow_fork;}
+                if launch.stop_at.is_none() {launch.stop_at = default.stop_at;}
+                if launch.no_aslr.is_none() {launch.no_aslr = default.no_aslr;}
+            }
+        }
+
+        match &mut self.record {
+            None => self.record = default.record,
+            Some(record) => {
+                let default = default.record.unwrap();
+                if record.history.is_none() {record.history = default.history;}
+            }
+        }
+
+        match &mut self.signals {
+            None => self.signals = default.signals,
+            Some(signals) => for (signal, disposition) in default.signals.unwrap() {
+                signals.entry(signal).or_insert(disposition);
+            }
+        }
+
         match &mut self.window {
             None => self.window = default.window,
             Some(window) => {
@@ -205,10 +320,16 @@ impl Config {
     }
 }
 
+pub fn config_dir() -> PathBuf { // ~/.config/tbd/, the user settings and the sessions
+    std::env::home_dir().unwrap_or_default().join(".config/tbd")
+}
+
+fn config_path() -> PathBuf { // we use the ~/.config/tbd/config.toml path for the user settings
+    config_dir().join("config.toml")
+}
+
 pub fn load_config() -> Config {
-    // we use the ~/.config/tbd/config.toml path for the user settings
-    let path = format!("{}/.config/tbd/config.toml", std::env::home_dir().unwrap_or(std::path::PathBuf::new()).to_str().unwrap());
-    match fs::read(path) {
+    match fs::read(config_path()) {
         Ok(file) => {
             let config: Result<Config, toml::de::Error> = toml::from_slice(&file);
             match config {
@@ -218,4 +339,24 @@ pub fn load_config() -> Config {
         }
         Err(_) => Config::default() // Default if we don't find the file or if there is a syntax error
     }
+}
+
+pub fn disposition(signal: Signal) -> Disposition {
+    match signal {
+        Signal::SIGTRAP | Signal::SIGSTOP => Disposition::stop, // breakpoints, steps and our own stops
+        _ => CONFIG.access().as_ref().unwrap().signals.as_ref().and_then(|signals| signals.get(signal.as_str()).copied()).unwrap_or(Disposition::stop)
+    }
+}
+
+pub fn save_signals() -> Result<(), std::io::Error> { // replaces only the signals table of the user config, the rest of the file (and its comments) stays
+    let invalid = |err: &dyn std::fmt::Display| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string());
+    let path = config_path();
+    let old = fs::read_to_string(&path).unwrap_or_default();
+    let mut document = old.parse::<toml_edit::DocumentMut>().map_err(|err| invalid(&err))?;
+
+    let signals = CONFIG.access().as_ref().unwrap().signals.clone().unwrap_or_default();
+    let table = toml::to_string(&signals).map_err(|err| invalid(&err))?.parse::<toml_edit::DocumentMut>().map_err(|err| invalid(&err))?;
+    document.insert("signals", toml_edit::Item::Table(table.as_table().clone())); // also replaces the dotted keys and inline tables of the signals
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, document.to_string())
 }
\ No newline at end of file
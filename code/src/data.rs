@@ -8,6 +8,8 @@ use std::{
 use crate::{
     config,
     dwarf,
+    syscall,
+    record,
     trace,
     ui
 };
@@ -34,9 +36,13 @@ pub static mut DATA: Vec<u8> = Vec::new(); // The executable file contents //AI
 type Global<T> = Mutex<Option<T>>; //AI
 
 pub static CONFIG: Global<config::Config> = empty();
+pub static LAUNCH: Global<config::Launch> = empty(); // of the loaded executable, the config with its session applied (edited in the Control pane, saved into the session)
 pub static FILE: Global<std::path::PathBuf> = empty();
 pub static STDIO: Global<std::os::fd::OwnedFd> = empty();
 pub static PID: Global<nix::unistd::Pid> = empty();
+pub static INFERIORS: Global<trace::Inferiors> = empty();
+pub static THREADS: Global<trace::Threads> = empty();
+pub static THREAD: Global<nix::unistd::Pid> = empty(); // thread selected for inspection (the REGISTERS belong to it)
 pub static PROC_PATH: Global<path::PathBuf> = empty();
 pub static MAPS: Global<Vec<trace::MemoryMap>> = empty();
 pub static EXEC_SHIFT: Global<u64> = empty();
@@ -46,9 +52,18 @@ pub static EHFRAME: Global<dwarf::EhFrame> = empty();
 pub static ENDIAN: Global<dwarf::Endian> = empty();
 pub static SOURCE: Global<dwarf::SourceMap> = empty();
 pub static LINES: Global<dwarf::LineAddresses> = empty();
+pub static PROLOGUES: Global<dwarf::Prologues> = empty();
 pub static FUNCTIONS: Global<dwarf::FunctionIndex> = empty();
 pub static BREAKPOINTS: Global<trace::Breakpoints> = empty();
+pub static WATCHPOINTS: Global<trace::Watchpoints> = empty(); // of the focused process, cleared with it
+pub static LIBRARIES: Global<Vec<dwarf::Library>> = empty(); // shared libraries of the tracee, kept between the runs so the images stay the same
+pub static LINKER: Global<trace::Linker> = empty(); // the dynamic linker hook, for following the library loads
+pub static CORE: Global<trace::Core> = empty(); // the loaded core dump, instead of a running tracee
+pub static SYSCALLS: Global<syscall::Tracing> = empty(); // syscall log and catchpoints, kept between the runs
+pub static CHECKPOINTS: Global<Vec<trace::Checkpoint>> = empty(); // the stopped copies of the tracee, killed with it
+pub static RECORD: Global<record::Recording> = empty(); // the recorded steps for the reverse execution, the switch is kept between the runs
 pub static REGISTERS: Global<nix::libc::user_regs_struct> = empty();
+pub static FPREGS: Global<trace::FpRegisters> = empty(); // x87 and vector registers of the selected thread, like the REGISTERS
 
 pub static SAVED_STATE: Global<SavedState> = empty(); // used for storing the sidebar panes
 
@@ -1,6 +1,7 @@
 use std::{
     path::PathBuf,
-    collections::HashMap
+    collections::{HashMap, HashSet},
+    sync::Arc
 };
 
 use gimli::{
@@ -11,7 +12,8 @@ use gimli::{
 
 use object::{
     Object,
-    ObjectSection
+    ObjectSection,
+    ObjectSegment
 };
 
 use iced_x86::{ // Disassembler
@@ -33,6 +35,7 @@ use crate::{
 /// FILE: dwarf.rs - Loading, parsing and displaying the debbuging data from the DWARF standard, unwinding the STACK and function calls
 
 const NOMASK: u64 = u64::MAX; // All bits set
+const IMAGE_SHIFT: u64 = 48; // normalized addresses of the shared libraries carry their image number above the user address space
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct SourceFile {
@@ -125,9 +128,8 @@ pub trait ImplLineAddresses<'a> {
 
 impl <'a> ImplLineAddresses<'a> for LineAddresses {
     fn get_line(&'a self, address: u64) -> Option<&'a SourceIndex> { // we get the line from the real address
-        match EXEC_SHIFT.access().as_ref() {
-            Some(shift) => if *shift > address {return None},
-            None => ()
+        if outside_images(address) {
+            return None;
         }
         self.get(&normal(address)) // normal to find the normalized address
     }
@@ -145,6 +147,8 @@ impl <'a> ImplLineAddresses<'a> for LineAddresses {
 }
 
 // gimli Type Definitions
+pub type Prologues = HashSet<u64>; // addresses where the LINE PROGRAMS mark the end of a function prologue
+
 pub type Endian = gimli::RunTimeEndian;
 type Section<'data> = std::borrow::Cow<'data, [u8]>;
 pub type DwarfSections<'data> = gimli::DwarfSections<Section<'data>>;
@@ -187,6 +191,59 @@ impl SectionsToDwarf for DwarfSections<'_> { // we cannot save the the borrow in
     }
 }
 
+pub struct Library { // a shared library mapped by the dynamic linker, its index + 1 is the image of its normalized addresses
+    pub path: PathBuf,
+    pub bias: Option<u64>, // load bias, None while unloaded (its breakpoints are pending)
+    pub size: u64, // end of the last loadable segment
+    pub debug: Option<Arc<LibraryDebug>>
+}
+
+pub struct LibraryDebug {
+    pub dwarf: DwarfSections<'static>,
+    pub ehframe: EhFrame<'static>
+}
+
+impl Library {
+    pub fn load(path: PathBuf, bias: u64, image: usize) -> Self { // reading the library and adding its debug data to the executables SOURCE, LINES and FUNCTIONS
+        let data = std::fs::read(&path).unwrap_or_default();
+        let (size, debug) = match object::File::parse(&*data) {
+            Ok(object) => (
+                object.segments().map(|segment| segment.address() + segment.size()).max().unwrap_or(0),
+                object.section_by_name(".debug_info").is_some() && object.section_by_name(".eh_frame").is_some()
+            ),
+            Err(_) => (0, false)
+        };
+        let endian = *ENDIAN.access();
+        let debug = match endian {
+            Some(endian) if debug => {
+                let data = Box::leak(Box::new(data)); // static like the executable DATA, kept for the next runs
+                load_dwarf(data).ok().map(|(dwarf, object)| {
+                    load_source(dwarf.dwarf(endian), image);
+                    parse_functions(dwarf.dwarf(endian), image);
+                    Arc::new(LibraryDebug {dwarf, ehframe: EhFrame::new(object)})
+                })
+            },
+            _ => None // without the debug data of the executable we dont need the libraries
+        };
+        Library {
+            path,
+            bias: Some(bias),
+            size,
+            debug
+        }
+    }
+}
+
+pub fn with_image<R>(image: usize, f: impl FnOnce(&EhFrame, &DwarfSections) -> R) -> Option<R> { // runs with the debug data of the executable (0) or of a shared library
+    if image == 0 {
+        let ehframe = EHFRAME.access();
+        let dwarf = DWARF.access();
+        return Some(f(ehframe.as_ref()?, dwarf.as_ref()?));
+    }
+    let debug = LIBRARIES.access().as_ref()?.get(image - 1)?.debug.clone()?; // not holding the LIBRARIES, the addresses get normalized inside
+    Some(f(&debug.ehframe, &debug.dwarf))
+}
+
 
 // READING THE DWARF DATA
 
@@ -210,11 +267,12 @@ pub fn load_dwarf(binary: &'static Vec<u8>) -> Result<(DwarfSections<'static>, o
 }
 
 
-pub fn load_source(dwarf: Dwarf) { // this funtion parses the line programs and creates the SourceMap and LineAddresses, gimli doesnt provide much better ways other than this, so ill leave comments
+pub fn load_source(dwarf: Dwarf, image: usize) { // this funtion parses the line programs and creates the SourceMap and LineAddresses, gimli doesnt provide much better ways other than this, so ill leave comments
 
-    // here we create the hashmaps
-    let mut source_files = SourceMap::new();
-    let mut line_addresses = LineAddresses::new();
+    // here we create the hashmaps, the shared libraries are added to the ones of the executable
+    let mut source_files = SOURCE.access().take().unwrap_or_default();
+    let mut line_addresses = LINES.access().take().unwrap_or_default();
+    let mut prologues = PROLOGUES.access().take().unwrap_or_default();
 
     //this is just type annotations so i wouldnt have to write them out in the closure definition
     type LineProgram<'a> = gimli::IncompleteLineProgram<EndianSlice<'a, Endian>, usize>;
@@ -261,7 +319,10 @@ pub fn load_source(dwarf: Dwarf) { // this funtion parses the line programs and
                 Some(line) => line.get(),
                 None => continue
             };
-            let address = row.address();
+            let address = image_address(image, row.address());
+            if row.prologue_end() {
+                prologues.insert(address);
+            }
 
             let source_file = SourceFile::new(rel_dir, unit.debug_info_offset().unwrap()); // we create the new SourceFile
 
@@ -282,6 +343,7 @@ pub fn load_source(dwarf: Dwarf) { // this funtion parses the line programs and
     // Setting the Globals
     SOURCE.sets(source_files);
     LINES.sets(line_addresses);
+    PROLOGUES.sets(prologues);
 }
 
 pub fn get_main_file() -> (String, String) { // getting the main file path from the main subprogram in the tracee
@@ -315,8 +377,9 @@ type FunctionRange = std::ops::Range<u64>; // pc range for the function
 
 pub struct FunctionIndex<Unit = DebugInfoOffset, FunctionOffset = DebugInfoOffset> {
     pub func_hash: HashMap<u64, FunctionOffset>, // address of the start of the range mapped with the correspoding subprogram entry in DWARF
-    pub range_hash: HashMap<Unit, Vec<FunctionRange>>, // for faster lookup of the function (since we know the unit from the LINE PROGRAMS)
-    pub subtype_parent: HashMap<FunctionOffset, String> //kinda optional, but VERY useful (saves the parent of the function (filename/class/namespace)
+    pub range_hash: HashMap<(usize, Unit), Vec<FunctionRange>>, // for faster lookup of the function (since we know the unit from the LINE PROGRAMS), the offsets are unique only in their image
+    pub subtype_parent: HashMap<(usize, FunctionOffset), String>, //kinda optional, but VERY useful (saves the parent of the function (filename/class/namespace)
+    pub names: Vec<(String, u64)> // qualified names (ns::Class::method, crate::module::fn) with the start of the function, for the function breakpoints
 }
 
 impl FunctionIndex {
@@ -324,11 +387,13 @@ impl FunctionIndex {
         FunctionIndex {
             func_hash: HashMap::new(),
             range_hash: HashMap::new(),
-            subtype_parent: HashMap::new()
+            subtype_parent: HashMap::new(),
+            names: Vec::new()
         }
     }
 
     fn insert_function(&mut self, range: FunctionRange, function_entry: DebugInfoOffset ,unit: DebugInfoOffset, subtype_parent: Option<&str>) {
+        let unit = (image(range.start), unit);
         let range_hash = &mut self.range_hash;
         if range_hash.contains_key(&unit) { // if we have the unit, we just push the new range, else we create a new vec with the range under the Unit
             range_hash.get_mut(&unit).unwrap().push(range.clone());
@@ -340,7 +405,7 @@ impl FunctionIndex {
         match subtype_parent { // if we find a parent, we insert it for the function info
             Some(str) => {
                 let parent_hash = &mut self.subtype_parent;
-                parent_hash.insert(function_entry, str.to_string());
+                parent_hash.insert((image(range.start), function_entry), str.to_string());
             },
             None => ()
         };
@@ -360,7 +425,7 @@ impl FunctionIndex {
     }
 
     fn find_range(&self, address: u64, unit: DebugInfoOffset) -> Option<&FunctionRange> { // finds the range of the current function
-        let ranges = &self.range_hash[&unit];
+        let ranges = self.range_hash.get(&(image(address), unit))?;
         for range in ranges {
             if range.contains(&address) {
                 return Some(range);
@@ -371,7 +436,7 @@ impl FunctionIndex {
 
     fn get_address(&self, function: DebugInfoOffset) -> Option<u64> { // we find the starting address for the function entry
         for (address, info_offset) in self.func_hash.clone().into_iter() {
-            if info_offset == function {
+            if info_offset == function && image(address) == 0 { // only used for the executable
                 return Some(address);
             }
         };
@@ -379,9 +444,10 @@ impl FunctionIndex {
     }
 }
 
-pub fn parse_functions(dwarf: Dwarf) { // parsing the subprogram entries to create the FunctionIndex
-    let mut function_index = FunctionIndex::new();
+pub fn parse_functions(dwarf: Dwarf, image: usize) { // parsing the subprogram entries to create the FunctionIndex
+    let mut function_index = FUNCTIONS.access().take().unwrap_or_else(FunctionIndex::new);
 
+    let mut declared_names: HashMap<DebugInfoOffset, String> = HashMap::new(); // the qualified names of the declarations, the definitions outside of the class use them
     let mut declarations: HashMap<DebugInfoOffset, &str>  = HashMap::new(); // mapping declaration function offset to its parent for later use, and they sit outside of units, in case some would be defined in different CU, although that is unlikely, also the reason for DebugInfoOffset instead
 
     let mut unit_headers = dwarf.units();
@@ -423,16 +489,30 @@ pub fn parse_functions(dwarf: Dwarf) { // parsing the subprogram entries to crea
             };
             if entry.tag() != gimli::DW_TAG_subprogram {continue;} // skipping over other entries
 
-            if entry.has_attr(gimli::DW_AT_main_subprogram) { // Finding main subprogram
+            if image == 0 && entry.has_attr(gimli::DW_AT_main_subprogram) { // Finding main subprogram
                 let main = entry.offset().to_debug_info_offset(&unit_header);
                 EHFRAME.access().as_mut().unwrap().main = main;
             };
 
+            let qualified = entry.attr_value(gimli::DW_AT_name).map(|name| { // the first parent is the compile unit
+                let mut path: Vec<&str> = parent_stack.iter().skip(1).copied().filter(|parent| !parent.is_empty()).collect();
+                path.push(string(name, &dwarf));
+                path.join("::")
+            });
+
             if entry.attr(gimli::DW_AT_declaration).is_some() { // save and skip declarations (no pc for us and such), they will be handled later
                 declarations.insert(entry.offset.to_debug_info_offset(&unit).unwrap(), parent_stack.last().unwrap_or(&""));
+                if let Some(qualified) = qualified {
+                    declared_names.insert(entry.offset.to_debug_info_offset(&unit).unwrap(), qualified);
+                }
                 continue;
             };
 
+            let qualified = match entry.attr(gimli::DW_AT_specification) {
+                Some(function_declaration) => declared_names.get(&debug_reference(function_declaration.value(), &unit)).cloned(),
+                None => qualified
+            };
+
             let parent: &str = match entry.attr(gimli::DW_AT_specification) { // if we have a specification, we want to get the parent of the declaration
                 Some(function_declaration) => *declarations.get(&debug_reference(function_declaration.value(), &unit)).unwrap(),
                 None => *parent_stack.last().unwrap_or(&"")
@@ -451,9 +531,14 @@ pub fn parse_functions(dwarf: Dwarf) { // parsing the subprogram entries to crea
                     unit.addr_base
                 ).expect("Range Parsing Error");
 
+                let mut first = true;
                 while let Some(range) = ranges.next().unwrap_or(None) { //iterating through the ranges
+                    if first && let Some(qualified) = &qualified { // the entry is in the first range
+                        function_index.names.push((qualified.clone(), image_address(image, range.begin)));
+                    }
+                    first = false;
                     function_index.insert_function(
-                        range.begin..range.end,
+                        image_address(image, range.begin)..image_address(image, range.end),
                         entry.offset.to_debug_info_offset(&unit).unwrap(),
                         unit.debug_info_offset().unwrap(),
                         applicable_parent
@@ -473,7 +558,10 @@ pub fn parse_functions(dwarf: Dwarf) { // parsing the subprogram entries to crea
                 None => continue
             };
 
-            let function_range = low_pc..low_pc+high_pc;
+            let function_range = image_address(image, low_pc)..image_address(image, low_pc+high_pc);
+            if let Some(qualified) = qualified {
+                function_index.names.push((qualified, function_range.start));
+            }
 
             function_index.insert_function(
                 function_range,
@@ -489,17 +577,121 @@ pub fn parse_functions(dwarf: Dwarf) { // parsing the subprogram entries to crea
 }
 
 pub fn find_main() -> DebugInfoOffset { // from the symbol, but only if main isnt already found (from parsing the functions)
+    let function_bind = &FUNCTIONS.access();
+    let functions = function_bind.as_ref().unwrap();
     let mut ehframe_bind = EHFRAME.access();
     let ehframe = ehframe_bind.as_mut().unwrap();
     let symbol = ehframe.object.symbol_by_name("main").unwrap();
     let address = object::ObjectSymbol::address(&symbol);
-    let function_bind = &FUNCTIONS.access();
-    let functions = function_bind.as_ref().unwrap();
     let function = functions.direct_address(address);
     ehframe.main = Some(function);
     function
 }
 
+pub fn function_matches(query: &str, limit: usize) -> Vec<String> { // fuzzy completion of the function names, the best matches first
+    let bind = FUNCTIONS.access();
+    let functions = match bind.as_ref() {
+        Some(functions) => functions,
+        None => return Vec::new()
+    };
+    let mut matches: Vec<(i64, &String)> = functions.names.iter().filter_map(|(name, _)| fuzzy_score(query, name).map(|score| (score, name))).collect();
+    matches.sort_by(|(score, name), (other_score, other)| other_score.cmp(score).then(name.cmp(other)));
+    matches.dedup_by(|(_, name), (_, other)| name == other);
+    matches.into_iter().take(limit).map(|(_, name)| name.clone()).collect()
+}
+
+fn fuzzy_score(query: &str, name: &str) -> Option<i64> { // the query has to be a subsequence of the name, consecutive characters and starts of the path segments score more
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut last: Option<usize> = None;
+    let query: Vec<char> = query.chars().filter(|char| !char.is_whitespace()).collect();
+    for query_char in &query {
+        let found = (position..name.len()).find(|i| name[*i].eq_ignore_ascii_case(query_char))?;
+        if last.is_some_and(|last| last + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || matches!(name[found - 1], ':' | '_' | '.') {
+            score += 3;
+        }
+        last = Some(found);
+        position = found + 1;
+    }
+    if name.iter().rev().take_while(|char| **char != ':').count() == query.len() && last == Some(name.len() - 1) { // exactly the name without the path
+        score += 20;
+    }
+    Some(score * 100 - name.len() as i64)
+}
+
+pub fn function_breakpoints(name: &str) -> Vec<(u64, SourceIndex)> { // the addresses after the prologue of every function with the name (overloads, statics in other files), locals are set up there, either the qualified name or just its last segment
+    let starts = match FUNCTIONS.access().as_ref() {
+        Some(functions) => function_starts(&functions.names, name),
+        None => return Vec::new()
+    };
+    starts.into_iter().filter_map(after_prologue).collect()
+}
+
+fn function_starts(names: &[(String, u64)], name: &str) -> Vec<u64> { // the exact qualified matches, or without them every function whose last segment is the name
+    let exact: Vec<u64> = names.iter().filter(|(qualified, _)| qualified == name).map(|(_, start)| *start).collect();
+    let mut starts = if exact.is_empty() {
+        names.iter().filter(|(qualified, _)| qualified.rsplit("::").next() == Some(name)).map(|(_, start)| *start).collect()
+    } else {
+        exact
+    };
+    starts.sort();
+    starts.dedup();
+    starts
+}
+
+pub fn main_breakpoint() -> Option<(u64, SourceIndex)> { // the address after the prologue of main, for stopping at main on launch
+    let main = EHFRAME.access().as_ref()?.main?;
+    let start = FUNCTIONS.access().as_ref()?.get_address(main)?;
+    after_prologue(start)
+}
+
+fn after_prologue(start: u64) -> Option<(u64, SourceIndex)> { // the first line after the prologue of the function starting at the address
+    let functions_bind = FUNCTIONS.access();
+    let functions = functions_bind.as_ref()?;
+    let range = functions.range_hash.values().flatten().find(|range| range.start == start)?.clone();
+
+    let lines_bind = LINES.access();
+    let lines = lines_bind.as_ref()?;
+    let mut addresses: Vec<&u64> = lines.keys().filter(|address| range.contains(address)).collect();
+    addresses.sort();
+    let first = lines.get(&start)?.line;
+
+    let prologue = PROLOGUES.access().as_ref().and_then(|prologues| addresses.iter().find(|address| prologues.contains(address)).map(|address| **address));
+    let address = prologue
+    .or_else(|| addresses.iter().find(|address| lines[address].line != first).map(|address| **address)) // otherwise the next line of the function
+    .or_else(|| addresses.get(1).map(|address| **address)) // or its second row (functions on a single line)
+    .unwrap_or(start);
+    Some((address, lines.get(&address)?.clone()))
+}
+
+pub fn function_name(address: u64) -> Option<String> { // name of the function containing the real address (without unwinding), None if outside of our DWARF
+    if outside_images(address) {
+        return None;
+    }
+    let address = normal(address);
+
+    let function_bind = FUNCTIONS.access();
+    let functions = function_bind.as_ref()?;
+    let ((image, unit), start) = functions.range_hash.iter().find_map(|(unit, ranges)| { // we dont know the unit, so we search all of them
+        ranges.iter().find(|range| range.contains(&address)).map(|range| (*unit, range.start))
+    })?;
+    let function = functions.direct_address(start);
+    drop(function_bind);
+
+    with_image(image, |_, dwarf| {
+        let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+        let dwarf_unit = dwarf.unit(dwarf.debug_info.header_from_offset(unit).ok()?).ok()?;
+        let entry = dwarf_unit.entry(function.to_unit_offset(&dwarf_unit)?).ok()?;
+
+        let (info, _) = extract_function_info(&entry, &dwarf, &dwarf_unit);
+        Some(info.name)
+    })?
+}
+
 
 // Struct for returning the context and the row in the FDE
 struct UnwindInfo {
@@ -513,6 +705,7 @@ fn get_unwind_for_address(address: u64, eh_frame: (&GimliEhFrame, &EhFrame) ) ->
     // getting the section base (gimli internal logic)
     let bases = gimli::BaseAddresses::default().set_eh_frame(eh_frame.1.section_base);
     // fetching the FDE
+    let address = file_address(address); // the eh_frame knows only its own image
     let fde = eh_frame.0.fde_for_address(&bases, address, gimli::EhFrame::cie_from_offset).unwrap();
 
     let mut res = UnwindInfo {
@@ -538,7 +731,7 @@ fn get_cfa(
         gimli::CfaRule::RegisterAndOffset {
             register,
             offset
-        } => Ok((*match_register(register, regs) as i64 + offset) as u64), // we get the register and add the offset (casting to avoid overflows)
+        } => Ok((*match_register(register, regs)? as i64 + offset) as u64), // we get the register and add the offset (casting to avoid overflows)
         gimli::CfaRule::Expression(expression) => {
             let expression = expression.get(eh_frame).unwrap();
             let piece = eval_expression(&expression, regs, None, None, encoding)?[0];
@@ -562,7 +755,9 @@ fn unwind_registers(
     for (reg, rule) in rules { // we iterate through the rules
         let value = unwind_register(rule, cfa, regs, eh_frame, encoding);
         match value {
-            Ok(value) => *match_register(reg, regs) = value,
+            Ok(value) => if let Ok(register) = match_register(reg, regs) { // the vector registers are not unwound
+                *register = value
+            },
             Err(_) => ()
         };
     };
@@ -598,15 +793,15 @@ fn unwind_register(
                 _ => panic!("Register Expression Error")
             })
         },
-        gimli::RegisterRule::Register(register) => Ok(*match_register(register, regs)),
+        gimli::RegisterRule::Register(register) => Ok(*match_register(register, regs)?),
         gimli::RegisterRule::Constant(value) => Ok(*value),
         gimli::RegisterRule::SameValue => Err(()),
         _ => unimplemented!("Unimplemented register unwind rule")
     }
 }
 
-fn match_register<'a>(register: &gimli::Register, regs: &'a mut nix::libc::user_regs_struct) -> &'a mut u64 { //we match the gimli registers to the user regs
-    match *register {
+fn match_register<'a>(register: &gimli::Register, regs: &'a mut nix::libc::user_regs_struct) -> Result<&'a mut u64, ()> { //we match the gimli registers to the user regs, the rest (vector registers without the FPREGS) cant be read
+    Ok(match *register {
         gimli::X86_64::RAX => &mut regs.rax,
         gimli::X86_64::RBX => &mut regs.rbx,
         gimli::X86_64::RCX => &mut regs.rcx,
@@ -624,7 +819,24 @@ fn match_register<'a>(register: &gimli::Register, regs: &'a mut nix::libc::user_
         gimli::X86_64::R14 => &mut regs.r14,
         gimli::X86_64::R15 => &mut regs.r15,
         gimli::X86_64::RA =>  &mut regs.rip,
-        _ => unimplemented!("Unimplemented Register Match")
+        _ => return Err(())
+    })
+}
+
+fn vector_register(register: &gimli::Register) -> Option<Vec<u8>> { // the DWARF numbers 17-32 are the XMM0-15, 33-40 the ST0-7, their values are in the FPREGS
+    let bind = FPREGS.access();
+    let fpregs = bind.as_ref()?;
+    match register.0 {
+        17..=32 => Some(fpregs.xmm[(register.0 - 17) as usize].to_vec()),
+        33..=40 => Some(fpregs.st[(register.0 - 33) as usize].to_vec()),
+        _ => None
+    }
+}
+
+fn register_value(register: &gimli::Register, regs: &mut nix::libc::user_regs_struct) -> Result<u64, ()> { // for the expressions, the vector registers give their lowest 8 bytes
+    match vector_register(register) {
+        Some(bytes) => Ok(slice_to_u64(&bytes[..8])),
+        None => Ok(*match_register(register, regs)?)
     }
 }
 
@@ -650,7 +862,7 @@ fn eval_expression<'a>(
                 result = evaluation.resume_with_memory(gimli::Value::U64(slice_to_u64(&data))).map_err(|_| ())?;
             },
             gimli::EvaluationResult::RequiresRegister { register, .. } => {
-                result = evaluation.resume_with_register(gimli::Value::U64(*match_register(&register, regs))).map_err(|_| ())?;
+                result = evaluation.resume_with_register(gimli::Value::U64(register_value(&register, regs)?)).map_err(|_| ())?;
             },
             gimli::EvaluationResult::RequiresFrameBase => {
                 result = evaluation.resume_with_frame_base(frame_base.ok_or(())?).map_err(|_| ())?;
@@ -659,7 +871,7 @@ fn eval_expression<'a>(
                 result = evaluation.resume_with_call_frame_cfa(cfa.ok_or(())?).map_err(|_| ())?;
             },
             gimli::EvaluationResult::RequiresRelocatedAddress(address) => {
-                let new = anti_normal(address);
+                let new = anti_normal(image_address(image(normal(regs.rip)), address)); // relative to the image of the function
                 result = evaluation.resume_with_relocated_address(new).map_err(|_| ())?;
             },
             gimli::EvaluationResult::RequiresEntryValue(expr) => {
@@ -697,27 +909,35 @@ impl CallStack {
         CallStack(Vec::new())
     }
 
-    pub fn stack_lines(stack: Result<Self, ()>) -> Result<Vec<(usize, String)>, ()> { // parses the CallStack into String lines to be displayed by the ui
+    pub fn stack_lines(stack: Result<Self, ()>) -> Result<Vec<StackLine>, ()> { // parses the CallStack into String lines to be displayed by the ui
         if stack.is_err() {
             return Err(());
         }
 
         let functions_bind = FUNCTIONS.access();
-        let dwarf_bind = DWARF.access();
-        let dwarf = dwarf_bind.as_ref().unwrap().dwarf(ENDIAN.access().unwrap());
 
         let mut stack = stack.unwrap().0;
         stack.reverse(); // reversing to display the MAIN on the top and as 0: function call
 
         let mut result = Vec::new(); // the final lines
+        let mut watch = Vec::new(); // the lines of variables in memory, that can be watched
 
-        for (call, function) in stack.iter().enumerate() { // iterate over function calls
-            function.lines(call, &mut result, &dwarf, functions_bind.as_ref().unwrap());
+        for (call, function) in stack.iter().enumerate() { // iterate over function calls, each with the debug data of its image
+            with_image(function.image, |_, dwarf| {
+                function.lines(call, &mut result, &mut watch, &dwarf.dwarf(ENDIAN.access().unwrap()), functions_bind.as_ref().unwrap());
+            });
         };
-        Ok(result)
+
+        let mut lines: Vec<StackLine> = result.into_iter().map(|(depth, line)| (depth, line, None)).collect();
+        for (index, watchpoint) in watch {
+            lines[index].2 = Some(watchpoint);
+        }
+        Ok(lines)
     }
 }
 
+pub type StackLine = (usize, String, Option<trace::Watchpoint>); // depth, text and the watchpoint for the variable on the line
+
 type Type = DebugInfoOffset;
 #[derive(Debug, Clone)]
 pub struct Function {
@@ -726,11 +946,12 @@ pub struct Function {
     pub variables: Option<Vec<Variable>>,
     pub return_type: Option<Type>,
     pub debug_info_offset: Option<DebugInfoOffset>,
+    pub image: usize // the executable or a shared library, whose DWARF the offsets belong to
 }
 
 impl Function {
-    pub fn lines(&self, call: usize, res: &mut Vec<(usize, String)>, dwarf: &Dwarf, functions: &FunctionIndex) { // lines for a function call
-        let parent = match functions.subtype_parent.get(&self.debug_info_offset.unwrap()) {
+    pub fn lines(&self, call: usize, res: &mut Vec<(usize, String)>, watch: &mut Vec<(usize, trace::Watchpoint)>, dwarf: &Dwarf, functions: &FunctionIndex) { // lines for a function call
+        let parent = match functions.subtype_parent.get(&(self.image, self.debug_info_offset.unwrap())) {
             Some(parent) => format!("{parent}::"),
             None => "".to_string()
         };
@@ -742,6 +963,9 @@ impl Function {
         if let Some(parameters) = &self.parameters { // if parameters, iterate over parameters
             res.push((0, format!("{call}: {}{}(", parent, self.name)));
             for param in parameters {
+                if let Some(watchpoint) = watch_location(&param.name, Some(&param.location), param.vtype, dwarf) {
+                    watch.push((res.len(), watchpoint));
+                }
                 let mut temp_buf = Vec::new();
                 let param_value = param.lines(&mut temp_buf, &dwarf);
 
@@ -757,6 +981,9 @@ impl Function {
 
         if let Some(variables) = &self.variables { // if variables, iterate over variables
             for var in variables {
+            if let Some(watchpoint) = watch_location(&var.name, var.location.as_ref(), var.vtype, dwarf) {
+                watch.push((res.len(), watchpoint));
+            }
             let mut temp_buf = Vec::new();
             let var_value = var.lines(&mut temp_buf, &dwarf);
 
@@ -767,6 +994,16 @@ impl Function {
     }
 }
 
+fn watch_location(name: &str, location: Option<&Location>, vtype: Type, dwarf: &Dwarf) -> Option<trace::Watchpoint> { // write watchpoint for a variable in memory, bigger ones get watched from the start, as much as the alignment allows
+    let address = match location {
+        Some(Location::Address(address)) => *address,
+        _ => return None
+    };
+    let size = byte_size(vtype, dwarf).ok()?;
+    let size = [8, 4, 2, 1].into_iter().find(|watch| *watch <= size && address % watch == 0)?;
+    Some(trace::Watchpoint::new(address, size as u8, trace::WatchKind::Write, Some(name.to_string())))
+}
+
 #[derive(Debug, Clone)]
 pub struct Variable {
     pub name: String,
@@ -812,22 +1049,20 @@ pub fn call_stack<'a>() -> Result<CallStack, ()> { // unwinds the entire callsta
     // Registers
     let mut registers = REGISTERS.access().unwrap();
     // GLOBAL Bindings
-    let ehframe = EHFRAME.access();
     let lines = LINES.access();
     let source = SOURCE.access();
     let functions = FUNCTIONS.access();
-    let dwarf = DWARF.access();
-
-    let bindings = (
-        ehframe.as_ref().unwrap(),
-        lines.as_ref().unwrap(),
-        source.as_ref().unwrap(),
-        functions.as_ref().unwrap(),
-        dwarf.as_ref().unwrap(),
-    );
 
     loop {
-        if unwind(&mut call_stack, bindings, &mut registers)? { // unwind a single function call, if true, we are at main, if err, we found an error
+        let image = image(normal(registers.rip)); // the calls can go through the shared libraries
+        let main = with_image(image, |ehframe, dwarf| unwind(&mut call_stack, (
+            ehframe,
+            lines.as_ref().unwrap(),
+            source.as_ref().unwrap(),
+            functions.as_ref().unwrap(),
+            dwarf,
+        ), &mut registers)).ok_or(())?;
+        if main? { // unwind a single function call, if true, we are at main, if err, we found an error
             break;
         };
     }
@@ -872,6 +1107,7 @@ fn unwind ( // unwinds a single function call, producing its name, variables, pa
     // processing the function info (name, parent, return type)
     let (mut function_info, frame_attribute) = extract_function_info(die, &dwarf, &dwarf_unit);
     function_info.debug_info_offset = Some(function);
+    function_info.image = image(normal(regs.rip));
 
 
     let unwind_info = get_unwind_for_address(normal(regs.rip), (&gimli_eh_frame, eh_frame)); // we get the Unwind info for out address
@@ -883,7 +1119,7 @@ fn unwind ( // unwinds a single function call, producing its name, variables, pa
         let frame_base = eval_expression(&expression, regs, Some(cfa), None, encoding)?[0];
         match frame_base.location {
             gimli::Location::Address { address } => Some(address),
-            gimli::Location::Register {register} => Some(*match_register(&register, regs)),
+            gimli::Location::Register {register} => Some(*match_register(&register, regs)?),
             _ => panic!("Unknown FrameBase Expression")
         }
     } else {
@@ -977,7 +1213,8 @@ fn extract_function_info<'a>(
         parameters: Some(Vec::new()),
         variables: Some(Vec::new()),
         return_type: return_type,
-        debug_info_offset: None
+        debug_info_offset: None,
+        image: 0
     }, frame_base)
 }
 
@@ -1093,7 +1330,7 @@ fn get_loclist_location(
     encoding: gimli::Encoding
 ) -> Result<Location, ()> {
     while let Some(entry) = loc_list.next().map_err(|_| ())? { // we get the entry, until we find the one, that contains our pc
-        if (entry.range.begin..entry.range.end).contains(&normal(regs.rip)) {
+        if (entry.range.begin..entry.range.end).contains(&file_address(normal(regs.rip))) {
             let expression = entry.data;
             let piece = eval_expression(&expression, regs, None, frame_base, encoding)?[0];
             match piece.location { // we return the value based on the evaluated expression
@@ -1108,7 +1345,7 @@ fn get_loclist_location(
 }
 
 fn check_for_main(info: &Function, eh_frame: &EhFrame) -> bool { // function because of language specifications, for now just name matching
-    info.debug_info_offset == eh_frame.main
+    info.image == 0 && info.debug_info_offset == eh_frame.main
 }
 
 fn get_next_line(mut rip: u64, lines: &LineAddresses) -> Result<(&SourceIndex, u64), ()> { // calls are unfortunately outside of the lines addresses, so im using backwards byte search to find the correct address, thankfully this is an O(n) operation, where n is at worst the iteration limit, which is really fast for me
@@ -1225,6 +1462,23 @@ impl <'a>BaseType<'a> {
         }
     }
 
+    fn number(&self, slice: &[u8], endian: Endian) -> Result<Value, ()> { // the value for expressions, instead of the displayed text
+        let bits = slice.len() as u32 * 8;
+        if bits == 0 || bits > 128 {
+            return Err(());
+        }
+        let raw = u128::from_bytes(slice, endian);
+        match self.encoding {
+            gimli::DW_ATE_float => match slice.len() {
+                4 => Ok(Value::Float(f32::from_bytes(slice, endian) as f64)),
+                8 => Ok(Value::Float(f64::from_bytes(slice, endian))),
+                _ => Err(())
+            },
+            gimli::DW_ATE_signed | gimli::DW_ATE_signed_char => Ok(Value::Int(((raw << (128 - bits)) as i128) >> (128 - bits))), // sign extension
+            _ => Ok(Value::Int(raw as i128))
+        }
+    }
+
     fn encoding(&self, slice: &[u8], endian: Endian) -> String { // base types contain encoding, which we have interpret to display the value correctly
         let byte_size = slice.len();
         match self.encoding {
@@ -1822,18 +2076,18 @@ fn location_memory(location: Location, size: BitByteSize, endian: Endian) -> Res
         BitByteSize::Bit(bit) => bit.div_ceil(8)
     };
     let res = match location { // we either read from memory or the register
+        Location::Register(register) if let Some(bytes) = vector_register(&register) => Vec::from(bytes.get(0..read_size as usize).ok_or(())?),
         Location::Register(register) => {
             let mut bind = REGISTERS.access();
-            let number = match_register(&register, bind.as_mut().unwrap());
+            let number = match_register(&register, bind.as_mut().unwrap())?;
             let data = match endian {
                 gimli::RunTimeEndian::Big => number.to_be_bytes(),
                 gimli::RunTimeEndian::Little => number.to_le_bytes()
             };
-            Vec::from(&data[0..read_size as usize])
+            Vec::from(data.get(0..read_size as usize).ok_or(())?)
         }
         Location::Address(address) => {
-            let slice = trace::read_memory(address, read_size as usize).expect("Memory Read Exception");
-            slice
+            trace::read_memory(address, read_size as usize)? // expressions can point anywhere
         }
     };
     if let BitByteSize::Bit(_) = size { // i have not seen any lang use it, even when compiled to optimize size (for now bit size is unimplemented but wont panic)
@@ -1844,6 +2098,432 @@ fn location_memory(location: Location, size: BitByteSize, endian: Endian) -> Res
 }
 
 
+// EXPRESSION VALUES (for the expressions in eval.rs)
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i128),
+    Float(f64),
+    Pointer(u64, Type), // address and the type it points to
+    Object(Location, Type), // structs and arrays stay in the tracee, until a member or an element is selected
+    Void // the result of the called functions without a return type
+}
+
+pub fn current_frame() -> Result<Function, ()> { // unwinds only the innermost function call (for its variables and parameters)
+    let mut registers = REGISTERS.access().ok_or(())?;
+    let lines = LINES.access();
+    let source = SOURCE.access();
+    let functions = FUNCTIONS.access();
+
+    let (lines, source, functions) = (lines.as_ref().ok_or(())?, source.as_ref().ok_or(())?, functions.as_ref().ok_or(())?);
+    let mut call_stack = CallStack::new();
+    with_image(image(normal(registers.rip)), |ehframe, dwarf| {
+        unwind(&mut call_stack, (ehframe, lines, source, functions, dwarf), &mut registers)
+    }).ok_or(())??;
+    call_stack.0.pop().ok_or(())
+}
+
+impl Function {
+    pub fn lookup(&self, name: &str) -> Option<Result<Value, ()>> { // value of a variable or a parameter, None if the function doesnt have one with the name
+        with_image(self.image, |_, dwarf| {
+            let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+
+            if let Some(var) = self.variables.iter().flatten().rev().find(|var| var.name == name) { // the latest declaration shadows the previous ones
+                return Some(match (&var.location, var.constant) {
+                    (Some(location), _) => load_value(location.clone(), var.vtype, &dwarf),
+                    (None, Some(constant)) => Ok(Value::Int(constant as i128)),
+                    (None, None) => Err(()) // optimized out
+                });
+            }
+            let param = self.parameters.iter().flatten().find(|param| param.name == name)?;
+            Some(load_value(param.location.clone(), param.vtype, &dwarf))
+        })?
+    }
+
+    pub fn address_of(&self, name: &str) -> Option<Result<Value, ()>> { // &variable, only for the ones in the memory
+        let (location, vtype) = match self.variables.iter().flatten().rev().find(|var| var.name == name) {
+            Some(var) => (var.location.clone(), var.vtype),
+            None => self.parameters.iter().flatten().find(|param| param.name == name).map(|param| (Some(param.location.clone()), param.vtype))?
+        };
+        Some(match location {
+            Some(Location::Address(address)) => Ok(Value::Pointer(address, vtype)),
+            _ => Err(())
+        })
+    }
+}
+
+fn with_current_dwarf<R>(f: impl FnOnce(&Dwarf) -> Result<R, ()>) -> Result<R, ()> { // the types of the evaluated values come from the image we are stopped in
+    let rip = REGISTERS.access().as_ref().ok_or(())?.rip;
+    with_image(image(normal(rip)), |_, dwarf| f(&dwarf.dwarf(ENDIAN.access().unwrap()))).ok_or(())?
+}
+
+pub fn member_value(object: Value, name: &str) -> Result<Value, ()> { // object.member
+    with_current_dwarf(|dwarf| {
+        let (address, vtype) = member_address(object, name, dwarf)?;
+        load_value(Location::Address(address), vtype, dwarf)
+    })
+}
+
+pub fn member_pointer(object: Value, name: &str) -> Result<Value, ()> { // &object.member
+    with_current_dwarf(|dwarf| member_address(object, name, dwarf).map(|(address, vtype)| Value::Pointer(address, vtype)))
+}
+
+fn member_address(object: Value, name: &str, dwarf: &Dwarf) -> Result<(u64, Type), ()> {
+    let (address, vtype) = match object {
+        Value::Object(Location::Address(address), vtype) => (address, vtype),
+        _ => return Err(())
+    };
+    match strip_type(vtype, dwarf)? {
+        TypeDisplay::Struct(structure) => {
+            let member = structure.members.iter().find(|member| member.name == name).ok_or(())?;
+            Ok((address + member.offset, member.vtype))
+        },
+        _ => Err(())
+    }
+}
+
+pub fn deref_value(address: u64, vtype: Type) -> Result<Value, ()> { // *pointer
+    with_current_dwarf(|dwarf| load_value(Location::Address(address), vtype, dwarf))
+}
+
+pub fn element_value(base: Value, index: i128) -> Result<Value, ()> { // array[index] or pointer[index]
+    with_current_dwarf(|dwarf| {
+        let (address, vtype) = element_address(base, index, dwarf)?;
+        load_value(Location::Address(address), vtype, dwarf)
+    })
+}
+
+pub fn element_pointer(base: Value, index: i128) -> Result<Value, ()> { // &array[index]
+    with_current_dwarf(|dwarf| element_address(base, index, dwarf).map(|(address, vtype)| Value::Pointer(address, vtype)))
+}
+
+fn element_address(base: Value, index: i128, dwarf: &Dwarf) -> Result<(u64, Type), ()> {
+    let (address, vtype) = match base {
+        Value::Pointer(address, vtype) => (address, vtype),
+        Value::Object(Location::Address(address), vtype) => match strip_type(vtype, dwarf)? {
+            TypeDisplay::Array(array) => (address, array.vtype),
+            _ => return Err(())
+        },
+        _ => return Err(())
+    };
+    let offset = index.checked_mul(byte_size(vtype, dwarf)? as i128).ok_or(())?;
+    Ok((address.wrapping_add(offset as u64), vtype))
+}
+
+pub fn type_size(vtype: Type) -> Result<u64, ()> { // for the pointer arithmetic
+    with_current_dwarf(|dwarf| byte_size(vtype, dwarf))
+}
+
+fn load_value(location: Location, vtype: Type, dwarf: &Dwarf) -> Result<Value, ()> { // scalars are read from the tracee right away
+    let endian = ENDIAN.access().unwrap();
+    match unwind_type(vtype, dwarf) {
+        TypeDisplay::Base(base) => {
+            let endian = base.endian.unwrap_or(endian);
+            let slice = location_memory(location, base.size, endian)?;
+            base.number(&slice, endian)
+        },
+        TypeDisplay::Pointer(pointer) => {
+            let slice = location_memory(location, BitByteSize::Byte(8), endian)?;
+            Ok(Value::Pointer(u64::from_bytes(&slice, endian), pointer.vtype))
+        },
+        TypeDisplay::Enum(enume) => {
+            let size = match enume.size {
+                Some(size) => size,
+                None => unwind_type(enume.vtype.ok_or(())?, dwarf).size(dwarf)
+            };
+            let slice = location_memory(location, size, endian)?;
+            Ok(Value::Int(u64::from_bytes(&slice, endian) as i128))
+        },
+        TypeDisplay::Modifier(modifier) => load_value(location, modifier.vtype, dwarf),
+        TypeDisplay::Def(typedef) => load_value(location, typedef.vtype.ok_or(())?, dwarf),
+        TypeDisplay::Array(_) | TypeDisplay::Struct(_) => Ok(Value::Object(location, vtype))
+    }
+}
+
+fn strip_type<'a>(mut vtype: Type, dwarf: &'a Dwarf) -> Result<TypeDisplay<'a>, ()> { // skipping the typedefs and modifiers to the underlying type
+    loop {
+        match unwind_type(vtype, dwarf) {
+            TypeDisplay::Modifier(modifier) => vtype = modifier.vtype,
+            TypeDisplay::Def(typedef) => vtype = typedef.vtype.ok_or(())?,
+            display => return Ok(display)
+        }
+    }
+}
+
+fn byte_size(vtype: Type, dwarf: &Dwarf) -> Result<u64, ()> { // size of a value of the type (TypeDisplay::size gives pointers the size of the pointed to type)
+    match strip_type(vtype, dwarf)? {
+        TypeDisplay::Pointer(_) => Ok(8),
+        display => match display.size(dwarf) {
+            BitByteSize::Byte(size) => Ok(size),
+            BitByteSize::Bit(_) => Err(())
+        }
+    }
+}
+
+pub fn type_name(vtype: Type) -> Result<String, ()> { // the struct name for the method calls
+    with_current_dwarf(|dwarf| Ok(strip_type(vtype, dwarf)?.name(dwarf)))
+}
+
+pub fn value_text(value: &Value) -> String { // the displayed result of an expression, the structs on a single line
+    match value {
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Pointer(address, _) => format!("<0x{:x}>", address),
+        Value::Void => "void".to_string(),
+        Value::Object(location, vtype) => with_current_dwarf(|dwarf| {
+            let mut res = Vec::new();
+            let first = unwind_type(*vtype, dwarf).value(location.clone(), &mut res, 0, dwarf);
+            Ok(res.into_iter().fold(first, |text, (_, line)| format!("{} {}", text, line)))
+        }).unwrap_or("?".to_string())
+    }
+}
+
+
+// INFERIOR CALLS (functions called from the expressions, System V ABI)
+
+fn call_candidate(names: &[(String, u64)], name: &str) -> Result<u64, String> { // the exact name, or the end of the qualified name for a qualified one (Type::method of a namespaced type), never a guess
+    let qualified = format!("::{}", name);
+    let mut exact: Vec<&(String, u64)> = names.iter().filter(|(function, _)| function == name).collect();
+    if exact.is_empty() && name.contains("::") {
+        exact = names.iter().filter(|(function, _)| function.ends_with(&qualified)).collect();
+    }
+    exact.sort_by_key(|(_, start)| *start);
+    exact.dedup_by_key(|(_, start)| *start); // the declarations and the definitions share the start
+    match exact.as_slice() {
+        [] => Err(format!("No function named '{}'", name)),
+        [(_, start)] => Ok(*start),
+        candidates => Err(format!("'{}' is ambiguous, it matches {}", name, candidates.iter().map(|(function, _)| function.as_str()).collect::<Vec<_>>().join(", ")))
+    }
+}
+
+pub fn call_function(name: &str, arguments: Vec<Value>) -> Result<Value, String> { // the arguments are converted to the types of the parameters, the result to the return type
+    let (start, function, unit) = {
+        let bind = FUNCTIONS.access();
+        let functions = bind.as_ref().ok_or("No functions were parsed")?;
+        let start = call_candidate(&functions.names, name)?;
+        let unit = functions.range_hash.iter().find(|(_, ranges)| ranges.iter().any(|range| range.start == start)).ok_or(format!("No function named '{}'", name))?.0.1;
+        (start, functions.direct_address(start), unit)
+    };
+    let rip = REGISTERS.access().as_ref().ok_or("The program is not stopped")?.rip;
+    if image(start) != image(normal(rip)) { // the types of the values are only valid in their own file
+        return Err(format!("'{}' is not in the same file as the current function", name));
+    }
+
+    let call = with_image(image(start), |_, dwarf| {
+        let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+        let dwarf_unit = dwarf.unit(dwarf.debug_info.header_from_offset(unit).map_err(|_| ())?).map_err(|_| ())?;
+        let offset = function.to_unit_offset(&dwarf_unit).ok_or(())?;
+        let entry = dwarf_unit.entry(offset).map_err(|_| ())?;
+        let (info, _) = extract_function_info(&entry, &dwarf, &dwarf_unit);
+
+        let mut parameters = Vec::new();
+        let mut tree = dwarf_unit.entries_tree(Some(offset)).map_err(|_| ())?;
+        let mut children = tree.root().map_err(|_| ())?.children();
+        while let Some(child) = children.next().map_err(|_| ())? {
+            if child.entry().tag() == gimli::DW_TAG_formal_parameter {
+                parameters.push(debug_reference(child.entry().attr_value(gimli::DW_AT_type).ok_or(())?, &dwarf_unit));
+            }
+        }
+        Ok::<_, ()>((info.return_type, parameters))
+    }).ok_or("No debug data for the function")?;
+    let (return_type, parameters) = call.map_err(|_| format!("Could not read the parameters of '{}'", name))?;
+    if parameters.len() != arguments.len() {
+        return Err(format!("'{}' takes {} arguments, {} given", name, parameters.len(), arguments.len()));
+    }
+
+    let (call, return_type) = with_image(image(start), |_, dwarf| {
+        let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+        let mut call = trace::Call {address: anti_normal(start), ..Default::default()};
+        if let Some(vtype) = return_type && let Ok(TypeDisplay::Struct(_) | TypeDisplay::Array(_)) = strip_type(vtype, &dwarf) {
+            let size = byte_size(vtype, &dwarf).map_err(|_| "Unknown size of the return type")?;
+            call.returns = if size > 16 {
+                trace::CallReturn::Memory(size)
+            } else {
+                trace::CallReturn::Struct(size, eightbytes(vtype, size, &dwarf).map_err(|_| "Unsupported return type")?)
+            };
+        }
+        for (index, (argument, parameter)) in arguments.into_iter().zip(parameters).enumerate() { // after the return, its memory takes the first integer register
+            marshal_argument(argument, parameter, &mut call, &dwarf).map_err(|error| format!("Argument {}: {}", index + 1, error))?;
+        }
+        Ok::<_, String>((call, return_type))
+    }).ok_or("No debug data for the function")??;
+
+    let result = trace::call_function(call)?;
+    let vtype = match return_type {
+        Some(vtype) => vtype,
+        None => return Ok(Value::Void)
+    };
+    with_image(image(start), |_, dwarf| {
+        let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+        match strip_type(vtype, &dwarf) {
+            Ok(TypeDisplay::Base(base)) => match (base.encoding, base.size) {
+                (gimli::DW_ATE_float, BitByteSize::Byte(size @ (4 | 8))) => base.number(&result.xmm0[..size as usize], gimli::RunTimeEndian::Little),
+                (gimli::DW_ATE_float, _) => Err(()), // long double is returned in st0
+                (_, BitByteSize::Byte(size @ 1..=8)) => base.number(&result.rax.to_le_bytes()[..size as usize], gimli::RunTimeEndian::Little),
+                _ => Err(())
+            },
+            Ok(TypeDisplay::Pointer(pointer)) => Ok(Value::Pointer(result.rax, pointer.vtype)),
+            Ok(TypeDisplay::Enum(_)) => {
+                let size = byte_size(vtype, &dwarf)?.clamp(1, 8);
+                Ok(Value::Int((result.rax & (u64::MAX >> (64 - size * 8))) as i128))
+            },
+            Ok(TypeDisplay::Struct(_) | TypeDisplay::Array(_)) => Ok(Value::Object(Location::Address(result.memory), vtype)),
+            _ => Err(())
+        }
+    }).ok_or("No debug data for the function")?.map_err(|_| "Unsupported return type".to_string())
+}
+
+fn marshal_argument(argument: Value, parameter: Type, call: &mut trace::Call, dwarf: &Dwarf) -> Result<(), String> { // sorting the argument into the integer or vector registers, or the stack
+    match strip_type(parameter, dwarf).map_err(|_| "Unknown parameter type")? {
+        TypeDisplay::Base(base) if base.encoding == gimli::DW_ATE_float => {
+            let value = match argument {
+                Value::Int(value) => value as f64,
+                Value::Float(value) => value,
+                _ => return Err("Expected a number".to_string())
+            };
+            call.float(match base.size {
+                BitByteSize::Byte(4) => (value as f32).to_bits() as u64,
+                BitByteSize::Byte(8) => value.to_bits(),
+                _ => return Err("long double is not supported".to_string())
+            });
+        },
+        TypeDisplay::Base(_) | TypeDisplay::Enum(_) => call.integer(match argument {
+            Value::Int(value) => value as u64,
+            Value::Float(value) => value as i64 as u64,
+            Value::Pointer(address, _) => address,
+            _ => return Err("Expected a number".to_string())
+        }),
+        TypeDisplay::Pointer(_) | TypeDisplay::Array(_) => call.integer(match argument {
+            Value::Pointer(address, _) => address,
+            Value::Int(value) => value as u64,
+            Value::Object(Location::Address(address), vtype) if let Ok(TypeDisplay::Array(_)) = strip_type(vtype, dwarf) => address, // arrays decay to pointers
+            _ => return Err("Expected a pointer".to_string())
+        }),
+        TypeDisplay::Struct(_) => {
+            let address = match argument {
+                Value::Object(Location::Address(address), _) => address,
+                _ => return Err("Expected a struct".to_string())
+            };
+            let size = byte_size(parameter, dwarf).map_err(|_| "Unknown size of the struct")?;
+            let mut bytes = trace::read_memory(address, size as usize).map_err(|_| format!("Could not read the memory at 0x{:x}", address))?;
+            bytes.resize(size.next_multiple_of(8) as usize, 0);
+            let words: Vec<u64> = bytes.chunks(8).map(|word| u64::from_le_bytes(word.try_into().unwrap())).collect();
+
+            let integers = call.integer_registers();
+            let classes = if size > 16 {None} else {eightbytes(parameter, size, dwarf).ok()};
+            match classes {
+                Some(sse) if call.integers.len() + sse.iter().filter(|sse| !**sse).count() <= integers && call.floats.len() + sse.iter().filter(|sse| **sse).count() <= trace::CALL_FLOATS => {
+                    for (word, sse) in words.into_iter().zip(sse) {
+                        if sse {call.floats.push(word)} else {call.integers.push(word)}
+                    }
+                },
+                _ => call.stack.extend(words) // the bigger structs are copied on the stack, and the small ones when the registers run out
+            }
+        },
+        _ => return Err("Unsupported parameter type".to_string())
+    }
+    Ok(())
+}
+
+fn eightbytes(vtype: Type, size: u64, dwarf: &Dwarf) -> Result<Vec<bool>, ()> { // the classes of the 8 byte parts of a small struct, true when all of its fields are floats (SSE)
+    let mut fields = Vec::new();
+    scalar_fields(vtype, 0, dwarf, &mut fields)?;
+    Ok((0..size.div_ceil(8)).map(|part| {
+        fields.iter().filter(|(offset, _)| offset / 8 == part).map(|(_, float)| *float).reduce(|all, float| all && float).unwrap_or(false)
+    }).collect())
+}
+
+fn scalar_fields(vtype: Type, offset: u64, dwarf: &Dwarf, fields: &mut Vec<(u64, bool)>) -> Result<(), ()> { // the offsets of the nested members and elements, and whether they are floats
+    match strip_type(vtype, dwarf)? {
+        TypeDisplay::Struct(structure) => for member in &structure.members {
+            scalar_fields(member.vtype, offset + member.offset, dwarf, fields)?;
+        },
+        TypeDisplay::Array(array) => {
+            let size = byte_size(array.vtype, dwarf)?;
+            for index in 0..array.count.ok_or(())? {
+                scalar_fields(array.vtype, offset + index * size, dwarf, fields)?;
+            }
+        },
+        TypeDisplay::Base(base) => fields.push((offset, base.encoding == gimli::DW_ATE_float)),
+        _ => fields.push((offset, false))
+    }
+    Ok(())
+}
+
+
+// SOURCE STEPPING (next and finish)
+
+#[derive(Debug, Clone)]
+pub struct StepFrame { // the function call we are stepping in
+    pub name: String,
+    pub range: FunctionRange, // normalized pc range of the function
+    pub cfa: u64, // tells recursive calls of the same function apart
+    pub return_address: u64, // real address in the caller
+    pub return_type: Option<Type>,
+    pub image: usize // whose DWARF the return type belongs to
+}
+
+pub fn step_frame(mut regs: nix::libc::user_regs_struct) -> Result<StepFrame, ()> { // the innermost function call, without its variables
+    let lines_bind = LINES.access();
+    let source_bind = SOURCE.access();
+    let functions_bind = FUNCTIONS.access();
+    let functions = functions_bind.as_ref().ok_or(())?;
+
+    let (index, _) = get_next_line(regs.rip, lines_bind.as_ref().ok_or(())?)?;
+    let unit = source_bind.as_ref().ok_or(())?.get(&index.hash_path).ok_or(())?[index.index].compile_unit;
+    let range = functions.find_range(normal(regs.rip), unit).ok_or(())?.clone();
+    let function = functions.direct_address(range.start);
+    let image = image(range.start);
+
+    with_image(image, |eh_frame, dwarf| {
+        let gimli_eh_frame = eh_frame.eh_frame();
+        let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+
+        let dwarf_unit = dwarf.unit(dwarf.debug_info.header_from_offset(unit).map_err(|_| ())?).map_err(|_| ())?;
+        let entry = dwarf_unit.entry(function.to_unit_offset(&dwarf_unit).ok_or(())?).map_err(|_| ())?;
+        let (info, _) = extract_function_info(&entry, &dwarf, &dwarf_unit);
+
+        let unwind_info = get_unwind_for_address(normal(regs.rip), (&gimli_eh_frame, eh_frame));
+        let encoding = dwarf_unit.encoding();
+        let cfa = get_cfa(&unwind_info, &mut regs, &gimli_eh_frame, encoding)?;
+        unwind_registers(&unwind_info, cfa, &mut regs, &gimli_eh_frame, encoding)?; // the rip becomes the return address
+
+        Ok(StepFrame {
+            name: info.name,
+            range,
+            cfa,
+            return_address: regs.rip,
+            return_type: info.return_type,
+            image
+        })
+    }).ok_or(())?
+}
+
+pub fn return_value(image: usize, vtype: Type, xmm0: [u8; 16]) -> String { // the value returned by a function that just finished (System V ABI)
+    with_image(image, |_, dwarf| {
+        let dwarf = dwarf.dwarf(ENDIAN.access().unwrap());
+        let display = unwind_type(vtype, &dwarf);
+        let name = display.name(&dwarf);
+        let value = match strip_type(vtype, &dwarf) {
+            Ok(TypeDisplay::Base(base)) if base.encoding == gimli::DW_ATE_float => match base.size { // floats are returned in xmm0
+                BitByteSize::Byte(size) if size <= 8 => base.encoding(&xmm0[..size as usize], gimli::RunTimeEndian::Little),
+                _ => "?".to_string()
+            },
+            Ok(TypeDisplay::Struct(_)) | Ok(TypeDisplay::Array(_)) => match byte_size(vtype, &dwarf) {
+                Ok(size) if size > 16 => { // bigger values are returned in the memory of the caller, rax holds its address
+                    let address = REGISTERS.access().as_ref().map_or(0, |regs| regs.rax);
+                    display.value(Location::Address(address), &mut Vec::new(), 0, &dwarf)
+                },
+                _ => "(in registers)".to_string()
+            },
+            Ok(_) => display.value(Location::Register(gimli::X86_64::RAX), &mut Vec::new(), 0, &dwarf),
+            Err(()) => "?".to_string()
+        };
+        format!("{} = {}", name, value)
+    }).unwrap_or("?".to_string())
+}
+
+
 // CODE DISASSEMBLY
 
 
@@ -1939,20 +2619,56 @@ pub fn disassemble_code(address: u64, bytes: &[u8]) -> Result<Assembly, ()> { //
 
 // HELPER FUNCTIONS
 
-pub fn normal(rip: u64) -> u64 { // subtracts the EXEC SHIFT to produce the normalize PC value
+pub fn normal(rip: u64) -> u64 { // subtracts the EXEC SHIFT to produce the normalize PC value, or the load bias of the shared library (tagged with its image)
+    if let Some((image, bias)) = library_of(rip) {
+        return image_address(image, rip - bias);
+    }
     match EXEC_SHIFT.access().as_ref() {
         Some(offset) => rip - offset,
         None => rip
     }
 }
 
-pub fn anti_normal(address: u64) -> u64 { // adds the EXEC SHIFT to produce the real address
+pub fn anti_normal(address: u64) -> u64 { // adds the EXEC SHIFT to produce the real address, or the load bias of its shared library
+    let image = image(address);
+    if image != 0 {
+        let bias = LIBRARIES.access().as_ref().and_then(|libraries| libraries.get(image - 1)?.bias).unwrap_or(0);
+        return bias + file_address(address);
+    }
     match EXEC_SHIFT.access().as_ref() {
         Some(offset) => address + offset,
         None => address
     }
 }
 
+pub fn image(address: u64) -> usize { // the image of a normalized address, 0 is the executable
+    (address >> IMAGE_SHIFT) as usize
+}
+
+fn image_address(image: usize, address: u64) -> u64 { // normalized address from the file address of the image
+    ((image as u64) << IMAGE_SHIFT) | address
+}
+
+fn file_address(address: u64) -> u64 { // normalized address without its image
+    address & ((1 << IMAGE_SHIFT) - 1)
+}
+
+pub fn image_loaded(address: u64) -> bool { // false for the normalized addresses of unloaded shared libraries (their breakpoints are pending)
+    let image = image(address);
+    image == 0 || LIBRARIES.access().as_ref().and_then(|libraries| libraries.get(image - 1)?.bias).is_some()
+}
+
+fn library_of(rip: u64) -> Option<(usize, u64)> { // the image and the load bias of the shared library containing the real address
+    LIBRARIES.access().as_ref()?.iter().enumerate().find_map(|(index, library)| {
+        let bias = library.bias?;
+        (bias..bias + library.size).contains(&rip).then_some((index + 1, bias))
+    })
+}
+
+fn outside_images(address: u64) -> bool { // real address in none of the loaded files
+    EXEC_SHIFT.access().is_some_and(|shift| shift > address) && library_of(address).is_none()
+}
+
 fn get_unit_entry_offset(offset: DebugInfoOffset, dwarf: &Dwarf) -> (gimli::UnitOffset, DebugInfoOffset) { // unit offset AND the offset of the Unit
     // UnitEntryOffset from DebugInfoOffset
     let mut units = dwarf.units();
@@ -2041,4 +2757,42 @@ macro_rules! impl_from_bytes { //AI
     };
 }
 
-impl_from_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64); // macro implemented for all of these values
\ No newline at end of file
+impl_from_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64); // macro implemented for all of these values
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[(&str, u64)]) -> Vec<(String, u64)> {
+        list.iter().map(|(name, start)| (name.to_string(), *start)).collect()
+    }
+
+    #[test]
+    fn fuzzy_subsequence() {
+        assert!(fuzzy_score("vln", "vec_len").is_some());
+        assert!(fuzzy_score("VEC len", "vec_len").is_some()); // case and whitespace are ignored
+        assert_eq!(fuzzy_score("lenv", "vec_len"), None);
+        assert_eq!(fuzzy_score("x", "vec_len"), None);
+    }
+
+    #[test]
+    fn fuzzy_ranking() {
+        let score = |query, name| fuzzy_score(query, name).unwrap();
+        assert!(score("len", "vec::len") > score("len", "vec::length")); // the exact last segment first
+        assert!(score("len", "len") > score("len", "vec::len")); // then the shorter name
+        assert!(score("vl", "vec_len") > score("vl", "vcxl")); // starts of the segments
+        assert!(score("len", "a::len") > score("len", "a::lxexn")); // consecutive characters
+        assert_eq!(score("čaj", "x::čaj"), score("caj", "x::caj")); // the exact name counted in chars, not bytes
+    }
+
+    #[test]
+    fn function_names() {
+        let list = names(&[("main", 0x10), ("a::run", 0x20), ("b::run", 0x30), ("run", 0x40), ("a::run", 0x50), ("a::run", 0x20), ("c::running", 0x60)]);
+        assert_eq!(function_starts(&list, "run"), vec![0x40]); // the exact name wins over the last segments
+        assert_eq!(function_starts(&list, "a::run"), vec![0x20, 0x50]); // overloads, each function once
+        assert_eq!(function_starts(&names(&[("a::run", 0x20), ("b::run", 0x30), ("c::running", 0x60)]), "run"), vec![0x20, 0x30]);
+        assert_eq!(function_starts(&list, "b::ru"), Vec::<u64>::new());
+        assert_eq!(function_starts(&list, "missing"), Vec::<u64>::new());
+    }
+}
\ No newline at end of file
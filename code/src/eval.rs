@@ -0,0 +1,608 @@
+// internal imports
+use crate::dwarf::{
+    self,
+    Value
+};
+
+
+/// FILE: eval.rs - Parsing and evaluating C-like expressions (breakpoint conditions, watches and logpoint messages) in the current frame of the tracee
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(i128),
+    Float(f64),
+    Name(String),
+    Symbol(&'static str)
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::Name(name) => write!(f, "{}", name),
+            Self::Symbol(symbol) => write!(f, "{}", symbol)
+        }
+    }
+}
+
+const SYMBOLS: &[&str] = &[ // the two character symbols go first, so "->" doesnt get read as "-" and ">"
+    "->", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>",
+    "+", "-", "*", "/", "%", "<", ">", "!", "~", "&", "|", "^", "(", ")", "[", "]", ".", ","
+];
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Int(i128),
+    Float(f64),
+    Name(String),
+    Member(Box<Expr>, String), // a.b
+    Arrow(Box<Expr>, String), // a->b
+    Index(Box<Expr>, Box<Expr>), // a[b]
+    Call(Box<Expr>, Vec<Expr>), // f(a, b), a.f(b), a->f(b)
+    Unary(&'static str, Box<Expr>), // -a, !a, ~a, *a, &a
+    Binary(&'static str, Box<Expr>, Box<Expr>)
+}
+
+#[derive(Debug, Clone)]
+pub enum Part { // the pieces of a logpoint message
+    Text(String),
+    Expression(Expr)
+}
+
+// Parsing
+
+pub fn parse(text: &str) -> Result<Expr, String> { // checked when the condition is set, and parsed again on every hit
+    let tokens = tokenize(text)?;
+    if tokens.is_empty() {
+        return Err("Empty expression".to_string());
+    }
+    let mut parser = Parser {tokens, position: 0};
+    let expression = parser.binary(1)?;
+    match parser.next() {
+        Some(token) => Err(format!("Unexpected '{}'", token)),
+        None => Ok(expression)
+    }
+}
+
+pub fn parse_message(text: &str) -> Result<Vec<Part>, String> { // "i={i} buf={buf}", the braces are escaped by doubling them
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '{' if chars.peek() == Some(&'{') => {chars.next(); literal.push('{');},
+            '}' if chars.peek() == Some(&'}') => {chars.next(); literal.push('}');},
+            '{' => {
+                let mut expression = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(char) => expression.push(char),
+                        None => return Err("Unclosed '{' in the message".to_string())
+                    }
+                }
+                if !literal.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut literal)));
+                }
+                parts.push(Part::Expression(parse(&expression)?));
+            },
+            '}' => return Err("Unmatched '}' in the message".to_string()),
+            char => literal.push(char)
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(Part::Text(literal));
+    }
+    Ok(parts)
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let current = chars[index];
+        if current.is_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        if current.is_ascii_digit() { // numbers (decimal, 0x hex and floats)
+            let start = index;
+            while index < chars.len() && (chars[index].is_ascii_alphanumeric() || chars[index] == '.') {
+                index += 1;
+            }
+            let literal: String = chars[start..index].iter().collect();
+            tokens.push(number(&literal)?);
+            continue;
+        }
+
+        if current.is_alphabetic() || current == '_' { // variable and member names
+            let start = index;
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            tokens.push(Token::Name(chars[start..index].iter().collect()));
+            continue;
+        }
+
+        if current == '\'' { // character literal, eg. 'a'
+            match (chars.get(index + 1), chars.get(index + 2)) {
+                (Some(character), Some('\'')) => tokens.push(Token::Int(*character as i128)),
+                _ => return Err("Unterminated character literal".to_string())
+            }
+            index += 3;
+            continue;
+        }
+
+        let rest: String = chars[index..].iter().take(2).collect();
+        match SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            Some(symbol) => {
+                tokens.push(Token::Symbol(symbol));
+                index += symbol.len();
+            },
+            None => return Err(format!("Unexpected character '{}'", current))
+        }
+    }
+    Ok(tokens)
+}
+
+fn number(literal: &str) -> Result<Token, String> {
+    let parsed = if let Some(hex) = literal.strip_prefix("0x").or(literal.strip_prefix("0X")) {
+        i128::from_str_radix(hex, 16).map(Token::Int).ok()
+    } else if literal.contains('.') {
+        literal.parse::<f64>().map(Token::Float).ok()
+    } else {
+        literal.parse::<i128>().map(Token::Int).ok()
+    };
+    parsed.ok_or(format!("Invalid number '{}'", literal))
+}
+
+fn precedence(symbol: &str) -> Option<u8> { // binary operators, with the precedence of C
+    Some(match symbol {
+        "||" => 1,
+        "&&" => 2,
+        "|" => 3,
+        "^" => 4,
+        "&" => 5,
+        "==" | "!=" => 6,
+        "<" | "<=" | ">" | ">=" => 7,
+        "<<" | ">>" => 8,
+        "+" | "-" => 9,
+        "*" | "/" | "%" => 10,
+        _ => return None
+    })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_symbol(&self) -> Option<&'static str> {
+        match self.tokens.get(self.position) {
+            Some(Token::Symbol(symbol)) => Some(symbol),
+            _ => None
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Symbol(next)) if next == symbol => Ok(()),
+            Some(token) => Err(format!("Expected '{}', found '{}'", symbol, token)),
+            None => Err(format!("Expected '{}' at the end", symbol))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Name(name)) => Ok(name),
+            Some(token) => Err(format!("Expected a member name, found '{}'", token)),
+            None => Err("Expected a member name at the end".to_string())
+        }
+    }
+
+    fn binary(&mut self, min: u8) -> Result<Expr, String> { // precedence climbing, operators of the same level are left associative
+        let mut left = self.unary()?;
+        loop {
+            let (symbol, level) = match self.peek_symbol().and_then(|symbol| precedence(symbol).map(|level| (symbol, level))) {
+                Some((symbol, level)) if level >= min => (symbol, level),
+                _ => return Ok(left)
+            };
+            self.position += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(symbol, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.peek_symbol() {
+            Some(symbol @ ("-" | "!" | "~" | "*" | "&")) => {
+                self.position += 1;
+                Ok(Expr::Unary(symbol, Box::new(self.unary()?)))
+            },
+            _ => self.postfix()
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let mut expression = self.primary()?;
+        loop {
+            expression = match self.peek_symbol() {
+                Some(".") => {
+                    self.position += 1;
+                    Expr::Member(Box::new(expression), self.name()?)
+                },
+                Some("->") => {
+                    self.position += 1;
+                    Expr::Arrow(Box::new(expression), self.name()?)
+                },
+                Some("[") => {
+                    self.position += 1;
+                    let index = self.binary(1)?;
+                    self.expect("]")?;
+                    Expr::Index(Box::new(expression), Box::new(index))
+                },
+                Some("(") => {
+                    self.position += 1;
+                    let mut arguments = Vec::new();
+                    if self.peek_symbol() != Some(")") {
+                        arguments.push(self.binary(1)?);
+                        while self.peek_symbol() == Some(",") {
+                            self.position += 1;
+                            arguments.push(self.binary(1)?);
+                        }
+                    }
+                    self.expect(")")?;
+                    Expr::Call(Box::new(expression), arguments)
+                },
+                _ => return Ok(expression)
+            };
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Int(value)) => Ok(Expr::Int(value)),
+            Some(Token::Float(value)) => Ok(Expr::Float(value)),
+            Some(Token::Name(name)) => Ok(Expr::Name(name)),
+            Some(Token::Symbol("(")) => {
+                let expression = self.binary(1)?;
+                self.expect(")")?;
+                Ok(expression)
+            },
+            Some(token) => Err(format!("Unexpected '{}'", token)),
+            None => Err("Unexpected end of the expression".to_string())
+        }
+    }
+}
+
+// Evaluation
+
+pub fn condition(expression: &Expr) -> Result<bool, String> { // evaluated in the innermost frame of the stopped thread (REGISTERS)
+    let frame = dwarf::current_frame().map_err(|_| "Could not unwind the current function".to_string())?;
+    truth(&evaluate(expression, &frame)?)
+}
+
+pub fn message(parts: &[Part]) -> String { // the frame is unwound once for all of the expressions, the ones that fail are shown as <error>
+    let frame = dwarf::current_frame().map_err(|_| "Could not unwind the current function".to_string());
+    parts.iter().map(|part| match part {
+        Part::Text(text) => text.clone(),
+        Part::Expression(expression) => match frame.as_ref().map_err(|err| err.clone()).and_then(|frame| evaluate(expression, frame)) {
+            Ok(value) => dwarf::value_text(&value),
+            Err(err) => format!("<{}>", err)
+        }
+    }).collect()
+}
+
+pub fn watch(text: &str) -> Result<String, String> { // the displayed value of a watched expression, the function calls included
+    let expression = parse(text)?;
+    let frame = dwarf::current_frame().map_err(|_| "Could not unwind the current function".to_string())?;
+    Ok(dwarf::value_text(&evaluate(&expression, &frame)?))
+}
+
+fn evaluate(expression: &Expr, frame: &dwarf::Function) -> Result<Value, String> {
+    match expression {
+        Expr::Int(value) => Ok(Value::Int(*value)),
+        Expr::Float(value) => Ok(Value::Float(*value)),
+        Expr::Name(name) => match frame.lookup(name) {
+            Some(value) => value.map_err(|_| format!("Could not read the value of '{}'", name)),
+            None => match name.as_str() { // the variables take precedence over the constants
+                "true" => Ok(Value::Int(1)),
+                "false" | "NULL" | "nullptr" => Ok(Value::Int(0)),
+                _ => Err(format!("No variable named '{}' in {}()", name, frame.name))
+            }
+        },
+        Expr::Member(object, name) => dwarf::member_value(evaluate(object, frame)?, name).map_err(|_| format!("Could not read the member '{}'", name)),
+        Expr::Arrow(pointer, name) => {
+            let object = deref(evaluate(pointer, frame)?)?;
+            dwarf::member_value(object, name).map_err(|_| format!("Could not read the member '{}'", name))
+        },
+        Expr::Index(base, index) => {
+            let index = match scalar(evaluate(index, frame)?)? {
+                Value::Int(index) => index,
+                _ => return Err("Index has to be an integer".to_string())
+            };
+            dwarf::element_value(evaluate(base, frame)?, index).map_err(|_| format!("Could not read the element [{}]", index))
+        },
+        Expr::Call(function, arguments) => {
+            let mut values = Vec::new();
+            let name = match &**function {
+                Expr::Name(name) => name.clone(),
+                Expr::Member(object, method) => { // methods get the address of the object as the first argument (this, self)
+                    let object = address(object, frame)?;
+                    values.push(object.clone());
+                    method_name(&object, method)?
+                },
+                Expr::Arrow(pointer, method) => {
+                    let object = evaluate(pointer, frame)?;
+                    values.push(object.clone());
+                    method_name(&object, method)?
+                },
+                _ => return Err("Only named functions can be called".to_string())
+            };
+            for argument in arguments {
+                values.push(evaluate(argument, frame)?);
+            }
+            dwarf::call_function(&name, values)
+        },
+        Expr::Unary("&", operand) => address(operand, frame),
+        Expr::Unary(symbol, operand) => unary(symbol, evaluate(operand, frame)?),
+        Expr::Binary("&&", left, right) => Ok(Value::Int((truth(&evaluate(left, frame)?)? && truth(&evaluate(right, frame)?)?) as i128)), // short circuit, so "p && p->len" is safe
+        Expr::Binary("||", left, right) => Ok(Value::Int((truth(&evaluate(left, frame)?)? || truth(&evaluate(right, frame)?)?) as i128)),
+        Expr::Binary(symbol, left, right) => binary(symbol, evaluate(left, frame)?, evaluate(right, frame)?)
+    }
+}
+
+fn address(expression: &Expr, frame: &dwarf::Function) -> Result<Value, String> { // &a, of the values in the memory
+    match expression {
+        Expr::Name(name) => match frame.address_of(name) {
+            Some(value) => value.map_err(|_| format!("'{}' is not in the memory", name)),
+            None => Err(format!("No variable named '{}' in {}()", name, frame.name))
+        },
+        Expr::Member(object, name) => dwarf::member_pointer(evaluate(object, frame)?, name).map_err(|_| format!("Could not find the member '{}'", name)),
+        Expr::Arrow(pointer, name) => {
+            let object = deref(evaluate(pointer, frame)?)?;
+            dwarf::member_pointer(object, name).map_err(|_| format!("Could not find the member '{}'", name))
+        },
+        Expr::Index(base, index) => {
+            let index = match scalar(evaluate(index, frame)?)? {
+                Value::Int(index) => index,
+                _ => return Err("Index has to be an integer".to_string())
+            };
+            dwarf::element_pointer(evaluate(base, frame)?, index).map_err(|_| format!("Could not find the element [{}]", index))
+        },
+        Expr::Unary("*", pointer) => match evaluate(pointer, frame)? {
+            pointer @ Value::Pointer(..) => Ok(pointer),
+            _ => Err("Only pointers can be dereferenced".to_string())
+        },
+        expression => match evaluate(expression, frame)? {
+            Value::Object(dwarf::Location::Address(address), vtype) => Ok(Value::Pointer(address, vtype)), // the results of the called functions
+            _ => Err("Only values in the memory have an address".to_string())
+        }
+    }
+}
+
+fn method_name(object: &Value, method: &str) -> Result<String, String> { // Type::method, without the type we would call any function with the name
+    match object {
+        Value::Pointer(_, vtype) => match dwarf::type_name(*vtype) {
+            Ok(name) if !name.is_empty() => Ok(format!("{}::{}", name, method)),
+            _ => Err(format!("Could not find the type of the object for the method '{}'", method))
+        },
+        _ => Err(format!("'{}' can only be called on an object", method))
+    }
+}
+
+fn deref(value: Value) -> Result<Value, String> {
+    match value {
+        Value::Pointer(address, vtype) => dwarf::deref_value(address, vtype).map_err(|_| format!("Could not read the memory at 0x{:x}", address)),
+        _ => Err("Only pointers can be dereferenced".to_string())
+    }
+}
+
+fn scalar(value: Value) -> Result<Value, String> { // pointers are compared by their address
+    match value {
+        Value::Pointer(address, _) => Ok(Value::Int(address as i128)),
+        Value::Object(..) => Err("Structs and arrays cannot be used as values".to_string()),
+        Value::Void => Err("The function does not return a value".to_string()),
+        value => Ok(value)
+    }
+}
+
+fn truth(value: &Value) -> Result<bool, String> {
+    match scalar(value.clone())? {
+        Value::Int(value) => Ok(value != 0),
+        Value::Float(value) => Ok(value != 0.),
+        _ => unreachable!()
+    }
+}
+
+fn float(value: Value) -> f64 {
+    match value {
+        Value::Int(value) => value as f64,
+        Value::Float(value) => value,
+        _ => unreachable!() // only after scalar()
+    }
+}
+
+fn unary(symbol: &str, operand: Value) -> Result<Value, String> {
+    if symbol == "*" {
+        return deref(operand);
+    }
+    match (symbol, scalar(operand)?) {
+        ("-", Value::Int(value)) => Ok(Value::Int(value.wrapping_neg())),
+        ("-", Value::Float(value)) => Ok(Value::Float(-value)),
+        ("~", Value::Int(value)) => Ok(Value::Int(!value)),
+        ("!", value) => Ok(Value::Int(!truth(&value)? as i128)),
+        (symbol, _) => Err(format!("Operator '{}' needs an integer", symbol))
+    }
+}
+
+fn binary(symbol: &str, left: Value, right: Value) -> Result<Value, String> {
+    if let (Value::Pointer(address, vtype), Value::Int(count), "+" | "-") = (&left, &right, symbol) { // pointer arithmetic, moves by the size of the pointed to type
+        let size = dwarf::type_size(*vtype).map_err(|_| "Could not get the size of the pointed to type".to_string())? as i128;
+        let offset = if symbol == "+" {count.wrapping_mul(size)} else {count.wrapping_mul(size).wrapping_neg()};
+        return Ok(Value::Pointer((*address as i128).wrapping_add(offset) as u64, *vtype));
+    }
+
+    match (scalar(left)?, scalar(right)?) {
+        (Value::Int(left), Value::Int(right)) => Ok(Value::Int(match symbol {
+            "+" => left.wrapping_add(right),
+            "-" => left.wrapping_sub(right),
+            "*" => left.wrapping_mul(right),
+            "/" => left.checked_div(right).ok_or("Division by zero")?,
+            "%" => left.checked_rem(right).ok_or("Division by zero")?,
+            "<<" => left.wrapping_shl(right as u32),
+            ">>" => left.wrapping_shr(right as u32),
+            "&" => left & right,
+            "|" => left | right,
+            "^" => left ^ right,
+            _ => compare(symbol, left.cmp(&right))?
+        })),
+        (left, right) => { // at least one of them is a float
+            let (left, right) = (float(left), float(right));
+            Ok(match symbol {
+                "+" => Value::Float(left + right),
+                "-" => Value::Float(left - right),
+                "*" => Value::Float(left * right),
+                "/" => Value::Float(left / right),
+                "%" => Value::Float(left % right),
+                "<<" | ">>" | "&" | "|" | "^" => return Err(format!("Operator '{}' needs integers", symbol)),
+                _ => match left.partial_cmp(&right) {
+                    Some(ordering) => Value::Int(compare(symbol, ordering)?),
+                    None => Value::Int((symbol == "!=") as i128) // NaN is not equal to anything
+                }
+            })
+        }
+    }
+}
+
+fn compare(symbol: &str, ordering: std::cmp::Ordering) -> Result<i128, String> {
+    Ok(match symbol {
+        "==" => ordering.is_eq(),
+        "!=" => ordering.is_ne(),
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        _ => return Err(format!("Unknown operator '{}'", symbol))
+    } as i128)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(expression: &Expr) -> String { // the parsed expression in prefix form, eg. (+ a (* b c))
+        match expression {
+            Expr::Int(value) => value.to_string(),
+            Expr::Float(value) => value.to_string(),
+            Expr::Name(name) => name.clone(),
+            Expr::Member(object, name) => format!("(. {} {})", tree(object), name),
+            Expr::Arrow(pointer, name) => format!("(-> {} {})", tree(pointer), name),
+            Expr::Index(base, index) => format!("([] {} {})", tree(base), tree(index)),
+            Expr::Call(function, arguments) => format!("(call {}{})", tree(function), arguments.iter().map(|argument| format!(" {}", tree(argument))).collect::<String>()),
+            Expr::Unary(symbol, operand) => format!("({}u {})", symbol, tree(operand)),
+            Expr::Binary(symbol, left, right) => format!("({} {} {})", symbol, tree(left), tree(right))
+        }
+    }
+
+    fn parsed(text: &str) -> String {
+        tree(&parse(text).unwrap())
+    }
+
+    fn int(value: Result<Value, String>) -> i128 {
+        match value {
+            Ok(Value::Int(value)) => value,
+            other => panic!("not an integer: {:?}", other)
+        }
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(tokenize("a->b >= 0x1F && c != 'x'").unwrap(), vec![
+            Token::Name("a".to_string()), Token::Symbol("->"), Token::Name("b".to_string()), Token::Symbol(">="), Token::Int(31),
+            Token::Symbol("&&"), Token::Name("c".to_string()), Token::Symbol("!="), Token::Int('x' as i128)
+        ]);
+        assert_eq!(tokenize("1.5<<_i2").unwrap(), vec![Token::Float(1.5), Token::Symbol("<<"), Token::Name("_i2".to_string())]);
+        assert_eq!(tokenize("  ").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn precedence_climbing() {
+        assert_eq!(parsed("a + b * c"), "(+ a (* b c))");
+        assert_eq!(parsed("a * b + c"), "(+ (* a b) c)");
+        assert_eq!(parsed("a - b - c"), "(- (- a b) c)"); // left associative
+        assert_eq!(parsed("(a - b) * c"), "(* (- a b) c)");
+        assert_eq!(parsed("a || b && c == d"), "(|| a (&& b (== c d)))");
+        assert_eq!(parsed("a & b == c"), "(& a (== b c))"); // like C, the comparison binds tighter
+        assert_eq!(parsed("a << 1 < b | c ^ d"), "(| (< (<< a 1) b) (^ c d))");
+    }
+
+    #[test]
+    fn unary_and_binary_operators() {
+        assert_eq!(parsed("-a - -b"), "(- (-u a) (-u b))");
+        assert_eq!(parsed("*p * *q"), "(* (*u p) (*u q))");
+        assert_eq!(parsed("&a & &b"), "(& (&u a) (&u b))");
+        assert_eq!(parsed("!~-x"), "(!u (~u (-u x)))");
+        assert_eq!(parsed("*p->next"), "(*u (-> p next))"); // postfix binds tighter than prefix
+        assert_eq!(parsed("-a[1]"), "(-u ([] a 1))");
+    }
+
+    #[test]
+    fn postfix() {
+        assert_eq!(parsed("p->next->value"), "(-> (-> p next) value)");
+        assert_eq!(parsed("a.b[i + 1].c"), "(. ([] (. a b) (+ i 1)) c)");
+        assert_eq!(parsed("m[i][j]"), "([] ([] m i) j)");
+        assert_eq!(parsed("s->len(1, x)"), "(call (-> s len) 1 x)");
+        assert_eq!(parsed("f()"), "(call f)");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("").unwrap_err(), "Empty expression");
+        assert_eq!(parse("a +").unwrap_err(), "Unexpected end of the expression");
+        assert_eq!(parse("a b").unwrap_err(), "Unexpected 'b'");
+        assert_eq!(parse("(a").unwrap_err(), "Expected ')' at the end");
+        assert_eq!(parse("a[1)").unwrap_err(), "Expected ']', found ')'");
+        assert_eq!(parse("a->1").unwrap_err(), "Expected a member name, found '1'");
+        assert_eq!(parse("a $ b").unwrap_err(), "Unexpected character '$'");
+        assert_eq!(parse("0x").unwrap_err(), "Invalid number '0x'");
+        assert_eq!(parse("'a").unwrap_err(), "Unterminated character literal");
+        assert_eq!(parse_message("{a").unwrap_err(), "Unclosed '{' in the message");
+        assert_eq!(parse_message("a}").unwrap_err(), "Unmatched '}' in the message");
+    }
+
+    #[test]
+    fn messages() {
+        let parts = parse_message("i={i} {{x}}").unwrap();
+        assert_eq!(parts.len(), 3);
+        assert!(matches!(&parts[0], Part::Text(text) if text == "i="));
+        assert!(matches!(&parts[1], Part::Expression(Expr::Name(name)) if name == "i"));
+        assert!(matches!(&parts[2], Part::Text(text) if text == " {x}"));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(int(binary("-", Value::Int(2), Value::Int(5))), -3);
+        assert_eq!(int(binary("<<", Value::Int(1), Value::Int(4))), 16);
+        assert_eq!(int(binary(">=", Value::Int(3), Value::Int(3))), 1);
+        assert_eq!(int(binary("<", Value::Float(0.5), Value::Int(1))), 1);
+        assert_eq!(int(unary("-", Value::Int(7))), -7);
+        assert_eq!(int(unary("!", Value::Int(0))), 1);
+        assert_eq!(binary("/", Value::Int(1), Value::Int(0)).unwrap_err(), "Division by zero");
+        assert_eq!(binary("&", Value::Float(1.), Value::Int(1)).unwrap_err(), "Operator '&' needs integers");
+        assert_eq!(unary("~", Value::Float(1.)).unwrap_err(), "Operator '~' needs an integer");
+        assert_eq!(unary("*", Value::Int(1)).unwrap_err(), "Only pointers can be dereferenced");
+    }
+}
@@ -7,8 +7,12 @@
 mod data;       // Globals Definition and Handling
 mod object;     // file handling, reading, preparing, (also responsible for terminal setup and running the Tracee)
 mod dwarf;      // local variables, call stack, background line tracking
+mod eval;       // expressions (breakpoint conditions) evaluated against the variables of the tracee
+mod syscall;    // syscall table, decoding the traced syscalls for the log and the catchpoints
+mod record;     // recording the steps for the reverse execution
 mod trace;      // debugging programs (eg. backend for the ui and debug functions)
 mod config;     // handling config and setting files located in ~/.config/tbd/
+mod session;    // breakpoints, watches, launch settings and the layout saved for each executable
 mod ui;         // user interface - communicating with user and graphics
 mod style;      // styling functions
 mod window;     // window handle
@@ -20,5 +24,6 @@ use crate::data::*;
 
 fn main() {
     CONFIG.sets(config::load_config());
+    LAUNCH.sets(CONFIG.access().as_ref().unwrap().launch.clone().unwrap());
     window::run_app().expect("Application failed to start");
 }
\ No newline at end of file
@@ -13,7 +13,10 @@ use std::{
 };
 
 use nix::{
-    sys::ptrace,
+    sys::{
+        ptrace,
+        personality::{self, Persona}
+    },
     pty,
     unistd::{
         fork,
@@ -24,13 +27,14 @@ use nix::{
 // internal import
 use crate::{
     data::*,
+    config,
     window::Dialog
 };
 
 
 /// FILE: object.rs - Managing communication with the filesystem
 
-pub fn run_tracee(file: &Path, args: Vec<String>, slave: Option<std::os::fd::OwnedFd>) -> Result<i32, ()> {
+pub fn run_tracee(file: &Path, launch: config::Launch, slave: Option<std::os::fd::OwnedFd>) -> Result<i32, ()> {
     let stdio= match slave { // creating Stdio from the internal terminal or the external ones
         Some(slave) =>  ( //AI
             Stdio::from(slave.try_clone().map_err(|_| ())?),
@@ -47,25 +51,45 @@ pub fn run_tracee(file: &Path, args: Vec<String>, slave: Option<std::os::fd::Own
     match unsafe {fork()} { // forking the program to create the child that will run PTRACE_TRACEME (so we can attach without su permissions)
         Ok(ForkResult::Parent { child }) => Ok(child.into()),
         Ok(ForkResult::Child) => {
-            let error = tracee_program(file, args, stdio);
-            process::exit(error.raw_os_error().unwrap());
+            let error = tracee_program(file, launch, stdio);
+            process::exit(error.raw_os_error().unwrap_or(1)); // errors of the Command (eg. a bad directory) have no errno
         },
         Err(_) => Err(())
     }
 }
 
-fn tracee_program(file: &Path, args: Vec<String>, stdio: (Stdio, Stdio, Stdio)) -> std::io::Error {
+fn tracee_program(file: &Path, launch: config::Launch, stdio: (Stdio, Stdio, Stdio)) -> std::io::Error {
     ptrace::traceme().unwrap_or_else(|err| {
         Dialog::error(&format!("Failed to execute ptrace on the tracee: {}", err), Some("Traceme error"));
         process::exit(-1)
     });
+    if launch.no_aslr.unwrap_or(false) { // the personality is kept through the exec, the stack, heap, libraries and the PIE base stop moving
+        personality::get().and_then(|persona| personality::set(persona | Persona::ADDR_NO_RANDOMIZE)).unwrap_or_else(|err| {
+            Dialog::error(&format!("Failed to disable ASLR for the tracee: {}", err), Some("Personality error"));
+            process::exit(-1)
+        });
+    }
 
-    process::Command::new(file) // .exec() will run execve syscall, replacing the entire memory of the child with the new executable (program we want to debug)
+    let mut command = process::Command::new(file);
+    command
     .stdin(stdio.0)
     .stdout(stdio.1)
     .stderr(stdio.2)
-    .args(args)
-    .exec()
+    .args(launch.args.unwrap_or_default());
+
+    if launch.clear_env.unwrap_or(false) { // only the variables we specify get passed
+        command.env_clear();
+    }
+    for variable in launch.env.unwrap_or_default() { // "KEY=VALUE", entries without '=' are skipped
+        if let Some((key, value)) = variable.split_once('=') {
+            command.env(key, value);
+        }
+    }
+    if let Some(directory) = launch.directory {
+        command.current_dir(directory);
+    }
+
+    command.exec() // .exec() will run execve syscall, replacing the entire memory of the child with the new executable (program we want to debug)
 }
 
 pub fn test_file(file: &Path) -> Result<(), ()> { // for testing if we have a correct file selected
@@ -87,6 +111,14 @@ pub fn test_file(file: &Path) -> Result<(), ()> { // for testing if we have a co
     Ok(())
 }
 
+pub fn entry_point() -> Option<u64> { // e_entry of the loaded file, the normalized address of the first instruction
+    #[allow(static_mut_refs)]
+    let data = unsafe { // static reference
+        &DATA
+    };
+    ::object::File::parse(data.as_slice()).ok().map(|file| ::object::Object::entry(&file))
+}
+
 pub fn open_child_stdio() -> Result<std::os::fd::OwnedFd, ()> { // retuns the FD of the slave, and sets the Global with the master FD
     let pty = match pty::openpty(None, None) { //AI
         Ok(pty) => pty,
@@ -113,6 +145,32 @@ pub fn read_source(file: &Path) -> Result<String, ()> {
     fs::read_to_string(file).map_err(|_| ())
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Process { // a running process we can attach to
+    pub pid: i32,
+    pub name: String
+}
+
+impl std::fmt::Display for Process {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} - {}", self.pid, self.name)
+    }
+}
+
+pub fn list_processes() -> Vec<Process> { // every process of the system (from /proc), except for us
+    let own = process::id() as i32;
+    let mut processes: Vec<Process> = fs::read_dir("/proc").into_iter().flatten().flatten().filter_map(|entry| {
+        let pid = entry.file_name().to_str()?.parse::<i32>().ok()?;
+        if pid == own {
+            return None;
+        }
+        let name = fs::read_to_string(entry.path().join("comm")).ok()?.trim_end().to_string();
+        Some(Process {pid, name})
+    }).collect();
+    processes.sort_by_key(|process| process.pid);
+    processes
+}
+
 pub async fn read_stdout() -> Result<(Vec<u8>, usize), ()> { // async read of the PTY stdout
     let mut buf = vec![0;256]; // 256 bytes per read
     let amount = stdio()?.read(&mut buf).map_err(|_| ())?;
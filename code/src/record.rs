@@ -0,0 +1,120 @@
+use std::{
+    collections::VecDeque,
+    os::unix::fs::FileExt
+};
+
+use iced_x86::{ // Disassembler, with the memory operands of the instructions
+    Decoder,
+    DecoderOptions,
+    InstructionInfoFactory,
+    OpAccess,
+    Register
+};
+
+use nix::libc::{user_regs_struct, user_fpregs_struct};
+
+// internal imports
+use crate::data::*;
+
+
+/// FILE: record.rs - Recording the steps of the tracee for the reverse execution, the registers and the memory every instruction overwrites
+const INSTRUCTION_SIZE: usize = 15; // the longest x86 instruction
+
+pub struct Snapshot { // the state before a single instruction
+    pub regs: user_regs_struct,
+    pub fpregs: Option<user_fpregs_struct>,
+    writes: Vec<(u64, Vec<u8>)> // the old bytes of the memory the instruction writes
+}
+
+#[derive(Default)]
+pub struct Recording { // the history of the recorded steps, dropped by everything that runs the tracee without recording
+    pub on: bool,
+    pub history: VecDeque<Snapshot>
+}
+
+impl Recording {
+    pub fn push(&mut self, snapshot: Snapshot) { // the oldest steps are forgotten over the limit
+        while self.history.len() >= history_limit() {
+            self.history.pop_front();
+        }
+        self.history.push_back(snapshot);
+    }
+}
+
+pub fn history_limit() -> usize {
+    CONFIG.access().as_ref().unwrap().record.as_ref().unwrap().history.unwrap().max(1)
+}
+
+pub fn snapshot(regs: user_regs_struct, fpregs: Option<user_fpregs_struct>) -> Snapshot { // saving what the next instruction changes, before it is stepped
+    let writes = written_memory(&regs).into_iter().filter_map(|(address, size)| {
+        let mut bytes = vec![0; size];
+        let bind = MEMORY.access();
+        bind.as_ref()?.read_exact_at(&mut bytes, address).ok()?; // unmapped memory faults, the instruction wont write it
+        Some((address, bytes))
+    }).collect();
+    Snapshot {regs, fpregs, writes}
+}
+
+pub fn restore_memory(snapshot: &Snapshot) -> Result<(), ()> { // writing back the old bytes, in the reverse order for the overlapping operands
+    let bind = MEMORY.access();
+    let memory = bind.as_ref().ok_or(())?;
+    for (address, bytes) in snapshot.writes.iter().rev() {
+        memory.write_all_at(bytes, *address).map_err(|_| ())?;
+    }
+    Ok(())
+}
+
+fn written_memory(regs: &user_regs_struct) -> Vec<(u64, usize)> { // the addresses and sizes of the memory operands the instruction writes (the pushes and calls included)
+    let mut code = vec![0; INSTRUCTION_SIZE];
+    let read = match MEMORY.access().as_ref().map(|memory| memory.read_at(&mut code, regs.rip)) { // the code can end before the full 15 bytes
+        Some(Ok(read)) => read,
+        _ => return Vec::new()
+    };
+    let mut decoder = Decoder::with_ip(64, &code[..read], regs.rip, DecoderOptions::NONE);
+    let instruction = decoder.decode();
+    if instruction.is_invalid() {
+        return Vec::new();
+    }
+
+    let mut factory = InstructionInfoFactory::new();
+    factory.info(&instruction).used_memory().iter().filter(|memory| matches!(
+        memory.access(),
+        OpAccess::Write | OpAccess::CondWrite | OpAccess::ReadWrite | OpAccess::ReadCondWrite
+    )).filter_map(|memory| {
+        let size = memory.memory_size().size(); // zero for the xsave like instructions, their size depends on the cpu
+        let address = memory.virtual_address(0, |register, _, _| register_value(regs, register))?; // the scatters (vsib) have no single address, they are not recorded
+        if size == 0 {None} else {Some((address, size))}
+    }).collect()
+}
+
+fn register_value(regs: &user_regs_struct, register: Register) -> Option<u64> { // the address registers of the operands, the segments are flat apart from the fs and gs (thread locals)
+    let value = match register.full_register() {
+        Register::RAX => regs.rax,
+        Register::RBX => regs.rbx,
+        Register::RCX => regs.rcx,
+        Register::RDX => regs.rdx,
+        Register::RSI => regs.rsi,
+        Register::RDI => regs.rdi,
+        Register::RBP => regs.rbp,
+        Register::RSP => regs.rsp,
+        Register::R8 => regs.r8,
+        Register::R9 => regs.r9,
+        Register::R10 => regs.r10,
+        Register::R11 => regs.r11,
+        Register::R12 => regs.r12,
+        Register::R13 => regs.r13,
+        Register::R14 => regs.r14,
+        Register::R15 => regs.r15,
+        Register::RIP => regs.rip,
+        Register::ES | Register::CS | Register::SS | Register::DS => return Some(0), // the segments are whole bases, not sized like the registers
+        Register::FS => return Some(regs.fs_base),
+        Register::GS => return Some(regs.gs_base),
+        _ => return None
+    };
+    Some(match register.size() {
+        1 => value & 0xff,
+        2 => value & 0xffff,
+        4 => value & 0xffff_ffff,
+        _ => value
+    })
+}
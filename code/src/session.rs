@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs,
+    path::{Path, PathBuf}
+};
+
+// internal imports
+use crate::{
+    data::*,
+    config,
+    dwarf::{
+        self,
+        ImplLineAddresses,
+        ImplSourceMap,
+        SourceIndex
+    },
+    trace::{
+        Breakpoint,
+        ImplBreakpoints
+    },
+    ui,
+    window
+};
+
+
+/// FILE: session.rs - Saving and restoring the session of each executable (breakpoints, watches, launch settings and the layout) in ~/.config/tbd/sessions/
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Session { // the executable is identified by its path, the build id would change with every rebuild
+    pub watches: Option<Vec<String>>,
+    pub launch: Option<config::Launch>,
+    pub layout: Option<config::Layout>,
+    pub breakpoints: Option<Vec<SessionBreakpoint>>
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SessionBreakpoint { // by the source line or the function name, the addresses move when the program is rebuilt
+    pub file: Option<String>,
+    pub line: Option<u64>,
+    pub function: Option<String>,
+    pub condition: Option<String>,
+    pub log: Option<String>,
+    pub ignore: Option<u64>,
+    pub enabled: Option<bool>
+}
+
+fn session_path(file: &Path) -> PathBuf { // the full path of the executable percent encoded, "home%2Fuser%2Fproject%2Fmain.toml" (the '%' first, so no two paths share a file)
+    let file = fs::canonicalize(file).unwrap_or(file.to_path_buf());
+    let name = file.to_string_lossy().trim_start_matches('/').replace('%', "%25").replace('/', "%2F");
+    config::config_dir().join("sessions").join(format!("{}.toml", name))
+}
+
+pub fn load(file: &Path) -> Option<Session> { // None without a saved session
+    let content = fs::read(session_path(file)).ok()?;
+    match toml::from_slice(&content) {
+        Ok(session) => Some(session),
+        Err(err) => {window::Dialog::error(&format!("Session syntax error: {}", err), Some("Session Loading Error")); None}
+    }
+}
+
+pub fn apply(state: &mut window::State, session: &Session) { // before the panes get preloaded with the new file
+    let mut current = CONFIG.access().as_ref().unwrap().launch.clone().unwrap(); // the settings of the previous executable are dropped
+    if let Some(launch) = session.launch.clone() {
+        if launch.args.is_some() {current.args = launch.args;}
+        if launch.env.is_some() {current.env = launch.env;}
+        if launch.clear_env.is_some() {current.clear_env = launch.clear_env;}
+        if launch.directory.is_some() {current.directory = launch.directory;}
+        if launch.follow_fork.is_some() {current.follow_fork = launch.follow_fork;}
+        if launch.stop_at.is_some() {current.stop_at = launch.stop_at;}
+        if launch.no_aslr.is_some() {current.no_aslr = launch.no_aslr;}
+    }
+    LAUNCH.sets(current);
+    if let Some(mut layout) = session.layout.clone() { // the left out fields are taken from the config
+        let default = CONFIG.access().as_ref().unwrap().layout.clone().unwrap();
+        layout.status_bar = layout.status_bar.or(default.status_bar);
+        layout.sidebar_left = layout.sidebar_left.or(default.sidebar_left);
+        layout.sidebar_right = layout.sidebar_right.or(default.sidebar_right);
+        layout.panel = layout.panel.or(default.panel);
+        layout.panel_mode = layout.panel_mode.or(default.panel_mode);
+        layout.panes = layout.panes.or(default.panes);
+        state.layout = ui::Layout::new(layout);
+    } else {
+        ui::reload_launch(state);
+    }
+    state.internal.pane.watches = session.watches.iter().flatten().map(|expression| (expression.clone(), String::new())).collect();
+    state.internal.pending_breakpoints = session.breakpoints.clone().unwrap_or_default(); // kept for the next save until they are restored
+}
+
+pub fn restore_breakpoints(state: &mut window::State) { // after the debug data is loaded, the ones we cannot find are kept for the next save
+    for saved in std::mem::take(&mut state.internal.pending_breakpoints) {
+        let addresses: Vec<u64> = match (&saved.function, &saved.file, saved.line) {
+            (Some(function), _, _) => dwarf::function_breakpoints(function).into_iter().map(|(address, _)| address).collect(), // every function with the name
+            (None, Some(file), Some(line)) => line_address(file, line).into_iter().collect(),
+            _ => Vec::new()
+        };
+        if addresses.is_empty() {
+            state.internal.pending_breakpoints.push(saved);
+            continue;
+        }
+
+        let mut bind = BREAKPOINTS.access();
+        let breakpoints = bind.get_or_insert_default();
+        for address in addresses {
+            breakpoints.add_future(address);
+            let breakpoint = breakpoints.get_mut(&address).unwrap();
+            breakpoint.condition = saved.condition.clone();
+            breakpoint.log = saved.log.clone();
+            breakpoint.ignore = saved.ignore.unwrap_or(0);
+            breakpoint.enabled = saved.enabled.unwrap_or(true);
+            breakpoint.function = saved.function.clone();
+        }
+    }
+}
+
+fn line_address(file: &str, line: u64) -> Option<u64> { // the normalized address of a line in a source file (its full path)
+    let lines_bind = LINES.access();
+    let source_bind = SOURCE.access();
+    let (lines, source) = (lines_bind.as_ref()?, source_bind.as_ref()?);
+    source.iter().find_map(|(hash_path, files)| {
+        let index = files.iter().position(|source_file| hash_path.join(&source_file.path) == Path::new(file))?;
+        lines.get_address(&SourceIndex {line, hash_path: hash_path.clone(), index})
+    })
+}
+
+pub fn save(state: &window::State) { // called after every change of the session, the errors are ignored (it would show a dialog on every click)
+    let file = match FILE.access().clone() {
+        Some(file) => file,
+        None => return
+    };
+    let session = Session {
+        watches: Some(state.internal.pane.watches.iter().map(|(expression, _)| expression.clone()).collect()),
+        launch: LAUNCH.access().clone(),
+        layout: Some(state.layout.session_layout()),
+        breakpoints: Some(breakpoints().into_iter().chain(state.internal.pending_breakpoints.iter().cloned()).collect())
+    };
+    let content = match toml::to_string(&session) {
+        Ok(content) => format!("# tbd session of {}\n{}", file.display(), content),
+        Err(_) => return
+    };
+
+    let path = session_path(&file);
+    if fs::create_dir_all(path.parent().unwrap()).is_ok() {
+        let _ = fs::write(path, content);
+    }
+}
+
+fn breakpoints() -> Vec<SessionBreakpoint> { // the breakpoints without a source line (set in the Assembly pane) and the temporary ones are not saved
+    let bind = BREAKPOINTS.access();
+    let current: Vec<(u64, Breakpoint)> = bind.iter().flatten().map(|(address, breakpoint)| (*address, breakpoint.clone())).collect();
+    drop(bind);
+
+    let lines_bind = LINES.access();
+    let source_bind = SOURCE.access();
+    let (lines, source) = match (lines_bind.as_ref(), source_bind.as_ref()) {
+        (Some(lines), Some(source)) => (lines, source),
+        _ => return Vec::new()
+    };
+    let mut saved: Vec<SessionBreakpoint> = current.into_iter().filter(|(_, breakpoint)| !breakpoint.temporary).filter_map(|(address, breakpoint)| {
+        let (file, line) = match &breakpoint.function {
+            Some(_) => (None, None),
+            None => {
+                let index = lines.get(&address)?;
+                (Some(index.hash_path.join(&source.index_with_line(index).path).to_string_lossy().to_string()), Some(index.line))
+            }
+        };
+        Some(SessionBreakpoint {
+            file,
+            line,
+            function: breakpoint.function,
+            condition: breakpoint.condition,
+            log: breakpoint.log,
+            ignore: Some(breakpoint.ignore),
+            enabled: Some(breakpoint.enabled)
+        })
+    }).collect();
+    saved.sort_by(|a, b| (&a.function, &a.file, a.line).cmp(&(&b.function, &b.file, b.line))); // the file stays the same when nothing changed
+    saved.dedup_by(|a, b| a.function.is_some() && a.function == b.function); // a function name is set on all of its functions at once
+    saved
+}
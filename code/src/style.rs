@@ -184,6 +184,33 @@ pub fn breakpoint_svg_toggled(theme: &Theme, _status: svg::Status) -> svg::Style
     }
 }
 
+pub fn breakpoint_svg_conditional(theme: &Theme, _status: svg::Status) -> svg::Style {
+    let pallete = theme.extended_palette();
+    svg::Style {
+        color: Some(color_mix(pallete.danger.base.color, pallete.success.base.color, 0.5))
+    }
+}
+
+pub fn breakpoint_svg_log(theme: &Theme, _status: svg::Status) -> svg::Style {
+    let pallete = theme.extended_palette();
+    svg::Style {
+        color: Some(color_mix(pallete.danger.base.color, pallete.primary.base.color, 0.5))
+    }
+}
+
+pub fn breakpoint_svg_temporary(theme: &Theme, _status: svg::Status) -> svg::Style {
+    let pallete = theme.extended_palette();
+    svg::Style {
+        color: Some(color_mix(pallete.danger.base.color, pallete.background.base.color, 0.4))
+    }
+}
+
+pub fn breakpoint_svg_disabled(theme: &Theme, _status: svg::Status) -> svg::Style {
+    svg::Style {
+        color: Some(theme.extended_palette().background.strong.color)
+    }
+}
+
 // just like breakpoint, but with different color
 pub fn collapse_svg(theme: &Theme, status: svg::Status) -> svg::Style {
     svg::Style {
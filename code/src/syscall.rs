@@ -0,0 +1,259 @@
+use nix::unistd::Pid;
+
+// internal imports
+use crate::trace;
+
+
+/// FILE: syscall.rs - The x86_64 system call table, decoding the arguments and the results of the traced syscalls
+// the arguments: f = file descriptor, s = string, b = buffer (its length is the next argument), p = pointer, i = signed, u = unsigned, x = flags (hex), o = mode (octal)
+const TABLE: [(&str, &str); 335] = [
+    ("read", "fpu"), ("write", "fbu"), ("open", "sxo"), ("close", "f"), ("stat", "sp"), ("fstat", "fp"), ("lstat", "sp"), ("poll", "pui"),
+    ("lseek", "fii"), ("mmap", "puxxfi"), ("mprotect", "pux"), ("munmap", "pu"), ("brk", "p"), ("rt_sigaction", "ippu"), ("rt_sigprocmask", "ippu"), ("rt_sigreturn", ""),
+    ("ioctl", "fxp"), ("pread64", "fpui"), ("pwrite64", "fbui"), ("readv", "fpu"), ("writev", "fpu"), ("access", "sx"), ("pipe", "p"), ("select", "ipppp"),
+    ("sched_yield", ""), ("mremap", "puuxp"), ("msync", "pux"), ("mincore", "pup"), ("madvise", "pui"), ("shmget", "iux"), ("shmat", "ipx"), ("shmctl", "iip"),
+    ("dup", "f"), ("dup2", "ff"), ("pause", ""), ("nanosleep", "pp"), ("getitimer", "ip"), ("alarm", "u"), ("setitimer", "ipp"), ("getpid", ""),
+    ("sendfile", "ffpu"), ("socket", "iii"), ("connect", "fpu"), ("accept", "fpp"), ("sendto", "fbuxpu"), ("recvfrom", "fpuxpp"), ("sendmsg", "fpx"), ("recvmsg", "fpx"),
+    ("shutdown", "fi"), ("bind", "fpu"), ("listen", "fi"), ("getsockname", "fpp"), ("getpeername", "fpp"), ("socketpair", "iiip"), ("setsockopt", "fiipu"), ("getsockopt", "fiipp"),
+    ("clone", "xpppp"), ("fork", ""), ("vfork", ""), ("execve", "spp"), ("exit", "i"), ("wait4", "ipxp"), ("kill", "ii"), ("uname", "p"),
+    ("semget", "iix"), ("semop", "ipu"), ("semctl", "iiix"), ("shmdt", "p"), ("msgget", "ix"), ("msgsnd", "ipux"), ("msgrcv", "ipuix"), ("msgctl", "iip"),
+    ("fcntl", "fix"), ("flock", "fi"), ("fsync", "f"), ("fdatasync", "f"), ("truncate", "si"), ("ftruncate", "fi"), ("getdents", "fpu"), ("getcwd", "pu"),
+    ("chdir", "s"), ("fchdir", "f"), ("rename", "ss"), ("mkdir", "so"), ("rmdir", "s"), ("creat", "so"), ("link", "ss"), ("unlink", "s"),
+    ("symlink", "ss"), ("readlink", "spu"), ("chmod", "so"), ("fchmod", "fo"), ("chown", "sii"), ("fchown", "fii"), ("lchown", "sii"), ("umask", "o"),
+    ("gettimeofday", "pp"), ("getrlimit", "ip"), ("getrusage", "ip"), ("sysinfo", "p"), ("times", "p"), ("ptrace", "iipp"), ("getuid", ""), ("syslog", "ipi"),
+    ("getgid", ""), ("setuid", "i"), ("setgid", "i"), ("geteuid", ""), ("getegid", ""), ("setpgid", "ii"), ("getppid", ""), ("getpgrp", ""),
+    ("setsid", ""), ("setreuid", "ii"), ("setregid", "ii"), ("getgroups", "ip"), ("setgroups", "ip"), ("setresuid", "iii"), ("getresuid", "ppp"), ("setresgid", "iii"),
+    ("getresgid", "ppp"), ("getpgid", "i"), ("setfsuid", "i"), ("setfsgid", "i"), ("getsid", "i"), ("capget", "pp"), ("capset", "pp"), ("rt_sigpending", "pu"),
+    ("rt_sigtimedwait", "pppu"), ("rt_sigqueueinfo", "iip"), ("rt_sigsuspend", "pu"), ("sigaltstack", "pp"), ("utime", "sp"), ("mknod", "sou"), ("uselib", "s"), ("personality", "x"),
+    ("ustat", "up"), ("statfs", "sp"), ("fstatfs", "fp"), ("sysfs", "iuu"), ("getpriority", "ii"), ("setpriority", "iii"), ("sched_setparam", "ip"), ("sched_getparam", "ip"),
+    ("sched_setscheduler", "iip"), ("sched_getscheduler", "i"), ("sched_get_priority_max", "i"), ("sched_get_priority_min", "i"), ("sched_rr_get_interval", "ip"), ("mlock", "pu"), ("munlock", "pu"), ("mlockall", "x"),
+    ("munlockall", ""), ("vhangup", ""), ("modify_ldt", "ipu"), ("pivot_root", "ss"), ("_sysctl", "p"), ("prctl", "ixxxx"), ("arch_prctl", "ix"), ("adjtimex", "p"),
+    ("setrlimit", "ip"), ("chroot", "s"), ("sync", ""), ("acct", "s"), ("settimeofday", "pp"), ("mount", "sssxp"), ("umount2", "sx"), ("swapon", "sx"),
+    ("swapoff", "s"), ("reboot", "iiip"), ("sethostname", "su"), ("setdomainname", "su"), ("iopl", "i"), ("ioperm", "uui"), ("create_module", "su"), ("init_module", "pus"),
+    ("delete_module", "sx"), ("get_kernel_syms", "p"), ("query_module", "sipup"), ("quotactl", "isip"), ("nfsservctl", "ipp"), ("getpmsg", "fpppp"), ("putpmsg", "fppii"), ("afs_syscall", ""),
+    ("tuxcall", ""), ("security", ""), ("gettid", ""), ("readahead", "fiu"), ("setxattr", "sspux"), ("lsetxattr", "sspux"), ("fsetxattr", "fspux"), ("getxattr", "sspu"),
+    ("lgetxattr", "sspu"), ("fgetxattr", "fspu"), ("listxattr", "spu"), ("llistxattr", "spu"), ("flistxattr", "fpu"), ("removexattr", "ss"), ("lremovexattr", "ss"), ("fremovexattr", "fs"),
+    ("tkill", "ii"), ("time", "p"), ("futex", "pxiupi"), ("sched_setaffinity", "iup"), ("sched_getaffinity", "iup"), ("set_thread_area", "p"), ("io_setup", "up"), ("io_destroy", "x"),
+    ("io_getevents", "xiipp"), ("io_submit", "xip"), ("io_cancel", "xpp"), ("get_thread_area", "p"), ("lookup_dcookie", "xpu"), ("epoll_create", "i"), ("epoll_ctl_old", ""), ("epoll_wait_old", ""),
+    ("remap_file_pages", "puuux"), ("getdents64", "fpu"), ("set_tid_address", "p"), ("restart_syscall", ""), ("semtimedop", "ipup"), ("fadvise64", "fiui"), ("timer_create", "ipp"), ("timer_settime", "ixpp"),
+    ("timer_gettime", "ip"), ("timer_getoverrun", "i"), ("timer_delete", "i"), ("clock_settime", "ip"), ("clock_gettime", "ip"), ("clock_getres", "ip"), ("clock_nanosleep", "ixpp"), ("exit_group", "i"),
+    ("epoll_wait", "fpii"), ("epoll_ctl", "fifp"), ("tgkill", "iii"), ("utimes", "sp"), ("vserver", ""), ("mbind", "puipux"), ("set_mempolicy", "ipu"), ("get_mempolicy", "ppupx"),
+    ("mq_open", "sxop"), ("mq_unlink", "s"), ("mq_timedsend", "fbuup"), ("mq_timedreceive", "fpupp"), ("mq_notify", "fp"), ("mq_getsetattr", "fpp"), ("kexec_load", "uupx"), ("waitid", "iipxp"),
+    ("add_key", "sspui"), ("request_key", "sssi"), ("keyctl", "ixxxx"), ("ioprio_set", "iii"), ("ioprio_get", "ii"), ("inotify_init", ""), ("inotify_add_watch", "fsx"), ("inotify_rm_watch", "fi"),
+    ("migrate_pages", "iupp"), ("openat", "fsxo"), ("mkdirat", "fso"), ("mknodat", "fsou"), ("fchownat", "fsiix"), ("futimesat", "fsp"), ("newfstatat", "fspx"), ("unlinkat", "fsx"),
+    ("renameat", "fsfs"), ("linkat", "fsfsx"), ("symlinkat", "sfs"), ("readlinkat", "fspu"), ("fchmodat", "fso"), ("faccessat", "fsx"), ("pselect6", "ippppp"), ("ppoll", "puppu"),
+    ("unshare", "x"), ("set_robust_list", "pu"), ("get_robust_list", "ipp"), ("splice", "fpfpux"), ("tee", "ffux"), ("sync_file_range", "fiix"), ("vmsplice", "fpux"), ("move_pages", "iupppx"),
+    ("utimensat", "fspx"), ("epoll_pwait", "fpiipu"), ("signalfd", "fpu"), ("timerfd_create", "ix"), ("eventfd", "u"), ("fallocate", "fxii"), ("timerfd_settime", "fxpp"), ("timerfd_gettime", "fp"),
+    ("accept4", "fppx"), ("signalfd4", "fpux"), ("eventfd2", "ux"), ("epoll_create1", "x"), ("dup3", "ffx"), ("pipe2", "px"), ("inotify_init1", "x"), ("preadv", "fpui"),
+    ("pwritev", "fpui"), ("rt_tgsigqueueinfo", "iiip"), ("perf_event_open", "piifx"), ("recvmmsg", "fpuxp"), ("fanotify_init", "xx"), ("fanotify_mark", "fxxfs"), ("prlimit64", "iipp"), ("name_to_handle_at", "fsppx"),
+    ("open_by_handle_at", "fpx"), ("clock_adjtime", "ip"), ("syncfs", "f"), ("sendmmsg", "fpux"), ("setns", "fx"), ("getcpu", "ppp"), ("process_vm_readv", "ipupux"), ("process_vm_writev", "ipupux"),
+    ("kcmp", "iiiuu"), ("finit_module", "fsx"), ("sched_setattr", "ipx"), ("sched_getattr", "ipux"), ("renameat2", "fsfsx"), ("seccomp", "uxp"), ("getrandom", "pux"), ("memfd_create", "sx"),
+    ("kexec_file_load", "ffusx"), ("bpf", "ipu"), ("execveat", "fsppx"), ("userfaultfd", "x"), ("membarrier", "ix"), ("mlock2", "pux"), ("copy_file_range", "fpfpux"), ("preadv2", "fpuix"),
+    ("pwritev2", "fpuix"), ("pkey_mprotect", "puxi"), ("pkey_alloc", "xx"), ("pkey_free", "i"), ("statx", "fsxxp"), ("io_pgetevents", "xiippp"), ("rseq", "puxx")
+];
+
+const TABLE_NEW: [(&str, &str); 39] = [ // the numbers from 424 on are shared by all of the architectures
+    ("pidfd_send_signal", "fipx"), ("io_uring_setup", "up"), ("io_uring_enter", "fuuxpu"), ("io_uring_register", "fupu"), ("open_tree", "fsx"), ("move_mount", "fsfsx"), ("fsopen", "sx"), ("fsconfig", "fispi"),
+    ("fsmount", "fxx"), ("fspick", "fsx"), ("pidfd_open", "ix"), ("clone3", "pu"), ("close_range", "uux"), ("openat2", "fspu"), ("pidfd_getfd", "fix"), ("faccessat2", "fsxx"),
+    ("process_madvise", "fpuix"), ("epoll_pwait2", "fpippu"), ("mount_setattr", "fsxpu"), ("quotactl_fd", "fiip"), ("landlock_create_ruleset", "pux"), ("landlock_add_rule", "fipx"), ("landlock_restrict_self", "fx"), ("memfd_secret", "x"),
+    ("process_mrelease", "fx"), ("futex_waitv", "puxpi"), ("set_mempolicy_home_node", "puux"), ("cachestat", "fppx"), ("fchmodat2", "fsox"), ("map_shadow_stack", "pux"), ("futex_wake", "pxix"), ("futex_wait", "pxxxpi"),
+    ("futex_requeue", "pxii"), ("statmount", "ppux"), ("listmount", "ppux"), ("lsm_get_self_attr", "ippx"), ("lsm_set_self_attr", "ipux"), ("lsm_list_modules", "ppx"), ("mseal", "pux")
+];
+const NEW_BASE: u64 = 424;
+
+const POINTER_RESULTS: &[&str] = &["mmap", "mremap", "brk", "shmat"]; // returning addresses instead of numbers
+const SHOWN_BYTES: usize = 32; // of the strings and buffers in the arguments
+
+fn entry(nr: u64) -> Option<(&'static str, &'static str)> {
+    match nr {
+        nr if nr < TABLE.len() as u64 => Some(TABLE[nr as usize]),
+        nr if nr >= NEW_BASE && nr - NEW_BASE < TABLE_NEW.len() as u64 => Some(TABLE_NEW[(nr - NEW_BASE) as usize]),
+        _ => None
+    }
+}
+
+pub fn name(nr: u64) -> String {
+    entry(nr).map_or(format!("syscall_{}", nr), |(name, _)| name.to_string())
+}
+
+pub fn number(name: &str) -> Option<u64> { // for the catchpoints, the unknown ones can be given as numbers
+    if let Some(index) = TABLE.iter().position(|(other, _)| *other == name) {
+        return Some(index as u64);
+    }
+    if let Some(index) = TABLE_NEW.iter().position(|(other, _)| *other == name) {
+        return Some(NEW_BASE + index as u64);
+    }
+    name.strip_prefix("syscall_").unwrap_or(name).parse().ok()
+}
+
+pub fn call(pid: Pid, nr: u64, args: [u64; 6]) -> String { // name(arguments) like strace shows them
+    let kinds = entry(nr).map_or("xxxxxx", |(_, kinds)| kinds);
+    let kinds: Vec<char> = kinds.chars().collect();
+    let args: Vec<String> = kinds.iter().enumerate().map(|(i, kind)| match kind {
+        'f' if args[i] as i32 == nix::libc::AT_FDCWD => String::from("AT_FDCWD"),
+        'f' | 'i' => format!("{}", args[i] as i64 as i32),
+        's' => quoted(read_bytes(pid, args[i], SHOWN_BYTES + 1, true), SHOWN_BYTES),
+        'b' => quoted(read_bytes(pid, args[i], args.get(i + 1).map_or(0, |len| (*len as usize).min(SHOWN_BYTES + 1)), false), SHOWN_BYTES),
+        'p' if args[i] == 0 => String::from("NULL"),
+        'o' => format!("0{:o}", args[i]),
+        'u' => format!("{}", args[i]),
+        _ => format!("0x{:x}", args[i])
+    }).collect();
+    format!("{}({})", name(nr), args.join(", "))
+}
+
+pub fn result(nr: u64, value: i64, error: bool) -> String { // the return value, errors with the errno name
+    if error {
+        let errno = nix::errno::Errno::from_raw(-value as i32);
+        return format!("-1 {:?} ({})", errno, errno.desc());
+    }
+    match entry(nr) {
+        Some((name, _)) if POINTER_RESULTS.contains(&name) => format!("0x{:x}", value),
+        _ => format!("{}", value)
+    }
+}
+
+fn read_bytes(pid: Pid, mut address: u64, len: usize, string: bool) -> Option<Vec<u8>> { // the strings end with a null byte, the buffers with their length
+    if address == 0 {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    while bytes.len() < len {
+        let word = trace::read_word(pid, address).ok()?.to_le_bytes();
+        if string && let Some(end) = word.iter().position(|byte| *byte == 0) {
+            bytes.extend_from_slice(&word[..end]);
+            return Some(bytes);
+        }
+        bytes.extend_from_slice(&word);
+        address += 8;
+    }
+    bytes.truncate(len);
+    Some(bytes)
+}
+
+fn quoted(bytes: Option<Vec<u8>>, limit: usize) -> String {
+    let bytes = match bytes {
+        Some(bytes) => bytes,
+        None => return String::from("NULL")
+    };
+    let text = String::from_utf8_lossy(&bytes[..bytes.len().min(limit)]).into_owned();
+    match bytes.len() > limit {
+        true => format!("{:?}...", text),
+        false => format!("{:?}", text)
+    }
+}
+
+// Catchpoints, stopping the tracee on the entry of a syscall
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catchpoint {
+    pub nr: u64,
+    pub fd: Option<i32> // only the calls on this file descriptor (the first argument)
+}
+
+impl Catchpoint {
+    pub fn parse(text: &str) -> Result<Self, String> { // "openat" or "write fd=2"
+        let mut words = text.split_whitespace();
+        let name = words.next().ok_or(String::from("Enter the name of the syscall."))?;
+        let nr = number(name).ok_or(format!("There is no syscall named {}.", name))?;
+        let fd = match words.next() {
+            Some(word) => match word.strip_prefix("fd=").and_then(|fd| fd.parse().ok()) {
+                Some(fd) => Some(fd),
+                None => return Err(format!("Expected fd=<number> after the syscall name, not {}.", word))
+            },
+            None => None
+        };
+        if let Some(word) = words.next() {
+            return Err(format!("Unexpected {} in the catchpoint.", word));
+        }
+        if fd.is_some() && !entry(nr).is_some_and(|(_, kinds)| kinds.starts_with('f')) {
+            return Err(format!("The first argument of {} is not a file descriptor.", name));
+        }
+        Ok(Catchpoint {nr, fd})
+    }
+
+    pub fn matches(&self, nr: u64, args: &[u64; 6]) -> bool {
+        self.nr == nr && self.fd.is_none_or(|fd| args[0] as i32 == fd)
+    }
+}
+
+impl std::fmt::Display for Catchpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.fd {
+            Some(fd) => write!(f, "{} fd={}", name(self.nr), fd),
+            None => write!(f, "{}", name(self.nr))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Tracing { // the tracee runs with PTRACE_SYSCALL while this logs or catches anything
+    pub log: bool,
+    pub catchpoints: Vec<Catchpoint>
+}
+
+impl Tracing {
+    pub fn active(&self) -> bool {
+        self.log || !self.catchpoints.is_empty()
+    }
+}
+
+// The log of the Syscalls pane
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub tid: Pid,
+    pub nr: u64,
+    pub call: String,
+    pub result: Option<String> // until the exit of the syscall
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.result {
+            Some(result) => write!(f, "[{}] {} = {}", self.tid, self.call, result),
+            None => write!(f, "[{}] {} ...", self.tid, self.call)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number("read"), Some(0));
+        assert_eq!(number("write"), Some(1));
+        assert_eq!(number("openat"), Some(257));
+        assert_eq!(number("clone3"), Some(435)); // the shared table after 424
+        assert_eq!(number("syscall_999"), Some(999)); // the unknown ones by their number
+        assert_eq!(number("12"), Some(12));
+        assert_eq!(number("nosuchcall"), None);
+        assert_eq!(name(257), "openat");
+        assert_eq!(name(435), "clone3");
+    }
+
+    #[test]
+    fn catchpoints() {
+        let catchpoint = Catchpoint::parse("write fd=2").unwrap();
+        assert_eq!((catchpoint.nr, catchpoint.fd), (1, Some(2)));
+        assert!(catchpoint.matches(1, &[2, 0, 0, 0, 0, 0]));
+        assert!(!catchpoint.matches(1, &[1, 0, 0, 0, 0, 0]));
+        assert!(!catchpoint.matches(0, &[2, 0, 0, 0, 0, 0]));
+        assert_eq!(catchpoint.to_string(), "write fd=2");
+
+        let catchpoint = Catchpoint::parse("  openat ").unwrap();
+        assert_eq!((catchpoint.nr, catchpoint.fd), (257, None));
+        assert!(catchpoint.matches(257, &[3, 0, 0, 0, 0, 0]));
+        assert_eq!(Catchpoint::parse("close fd=-1").unwrap().fd, Some(-1));
+    }
+
+    #[test]
+    fn catchpoint_errors() {
+        assert_eq!(Catchpoint::parse(" ").unwrap_err(), "Enter the name of the syscall.");
+        assert_eq!(Catchpoint::parse("nosuchcall").unwrap_err(), "There is no syscall named nosuchcall.");
+        assert_eq!(Catchpoint::parse("write 2").unwrap_err(), "Expected fd=<number> after the syscall name, not 2.");
+        assert_eq!(Catchpoint::parse("write fd=x").unwrap_err(), "Expected fd=<number> after the syscall name, not fd=x.");
+        assert_eq!(Catchpoint::parse("write fd=2 fd=3").unwrap_err(), "Unexpected fd=3 in the catchpoint.");
+        assert_eq!(Catchpoint::parse("getpid fd=1").unwrap_err(), "The first argument of getpid is not a file descriptor.");
+    }
+}
\ No newline at end of file
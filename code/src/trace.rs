@@ -1,9 +1,10 @@
 use std::{
     fs::File,
-    io::{Read, Seek},
+    io::{Read, Seek, Write},
+    os::unix::fs::FileExt,
     path::PathBuf,
     ffi::c_void,
-    collections::HashMap
+    collections::{HashMap, BTreeMap}
 };
 
 use nix::{
@@ -19,13 +20,18 @@ use nix::{
 
 use ::object as object_foreign;
 
-use object_foreign::Object;
+use object_foreign::{Object, ObjectSymbol};
 
 // internal import
 use crate::{
     data::*,
     dwarf::*,
+    eval,
     object,
+    syscall,
+    record,
+    session,
+    config,
     ui,
     window,
     window::Dialog
@@ -37,47 +43,147 @@ use crate::{
 // Signal info constants for hitting a breakpoint
 const SI_KERNEL: i32 = 0x80;
 const TRAP_BRKPT: i32 = 1;
+const SI_TKILL: i32 = -6; // sent by tgkill, our stops of the threads
+const TRAP_HWBKPT: i32 = 4; // one of the debug registers triggered
+const SYSCALL: [u8; 2] = [0x0f, 0x05]; // the instruction injected for the fork of a checkpoint
+
+// Inferior calls (System V ABI)
+const RED_ZONE: u64 = 128; // the leaf functions use the memory below the rsp
+pub const CALL_INTEGERS: usize = 6; // rdi, rsi, rdx, rcx, r8, r9
+pub const CALL_FLOATS: usize = 8; // xmm0-7
+const CALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+const LINK_LIMIT: usize = 4096; // iteration limit of the link_map list, in case it gets corrupted
+const SYSCALL_LOG_LIMIT: usize = 10000; // entries of the Syscalls pane, the oldest get dropped
+const SIGNAL_LOG_LIMIT: usize = 100; // printed signals in the Control pane
+
+// Debug registers in the user area (PTRACE_PEEKUSER and PTRACE_POKEUSER)
+const DEBUG_REGISTERS: usize = std::mem::offset_of!(nix::libc::user, u_debugreg);
+const DR6: usize = 6; // status, which of the DR0-DR3 triggered
+const DR7: usize = 7; // control, enable bits, types and lengths of the DR0-DR3
+
+// The XSAVE area of PTRACE_GETREGSET (NT_X86_XSTATE), in the standard format
+const NT_X86_XSTATE: usize = 0x202;
+const FXSAVE_SIZE: usize = 512; // the legacy area with the x87 and SSE registers
+const XSTATE_BV: usize = 512; // the header after the legacy FXSAVE area, which components are not in their initial state
+const XSTATE_YMM: usize = 576; // the upper halves of the YMM0-15
+const XSTATE_SIZE: usize = 4096; // the components after the AVX ones dont interest us, the kernel copies only what fits
 
 
-pub type Breakpoints = HashMap<u64, u8>;
+#[derive(Debug, Clone)]
+pub struct Breakpoint {
+    pub byte: u8, // the original byte, replaced by the 0xCC
+    pub condition: Option<String>, // expression (eval.rs), the tracee only stops when it is true
+    pub enabled: bool, // disabled breakpoints are kept, but not inserted
+    pub hits: u64, // stops (with a true condition) since the tracee started
+    pub ignore: u64, // the first hits continue without stopping
+    pub temporary: bool, // removed after its first stop
+    pub function: Option<String>, // set by the name of the function, the session saves the name instead of the line
+    pub log: Option<String>, // logpoints print the message (with the {expressions}) into the Log pane instead of stopping
+    inserted: bool // the 0xCC is in the tracee's code right now
+}
+
+impl Default for Breakpoint {
+    fn default() -> Self {
+        Breakpoint {
+            byte: 0,
+            condition: None,
+            enabled: true,
+            hits: 0,
+            ignore: 0,
+            temporary: false,
+            function: None,
+            log: None,
+            inserted: false
+        }
+    }
+}
+
+// A message of a logpoint hit, in the Log pane
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: std::time::Duration, // since the unix epoch
+    pub tid: Pid,
+    pub message: String
+}
+
+impl std::fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { // the time of the day in UTC, with milliseconds for the timing
+        let seconds = self.time.as_secs() % 86400;
+        write!(f, "{:02}:{:02}:{:02}.{:03} [{}] {}", seconds / 3600, seconds / 60 % 60, seconds % 60, self.time.subsec_millis(), self.tid, self.message)
+    }
+}
+
+pub type Breakpoints = HashMap<u64, Breakpoint>;
 
 pub trait ImplBreakpoints {
     fn add(&mut self, address: u64, byte: u8);
     fn add_future(&mut self, address: u64);
-    fn rem(&mut self, address: u64) -> u8;
-    fn disable_all(&self) -> Result<(), ()>;
+    fn rem(&mut self, address: u64) -> Result<(), ()>;
+    fn edit(&mut self, address: u64, settings: Breakpoint);
+    fn disable_all(&mut self) -> Result<(), ()>;
     fn enable_all(&mut self) -> Result<(), ()>;
 }
 
 impl ImplBreakpoints for Breakpoints {
-    fn add(&mut self, address: u64, byte: u8) {
-        self.insert(address, byte);
+    fn add(&mut self, address: u64, byte: u8) { // the breakpoint was already inserted, keeps the settings if it exists
+        let breakpoint = self.entry(address).or_default();
+        breakpoint.byte = byte;
+        breakpoint.inserted = true;
+    }
+
+    fn add_future(&mut self, address: u64) { // we save the location, it gets inserted on continue
+        self.entry(address).or_default();
     }
 
-    fn add_future(&mut self, address: u64) { // we save the location
-        self.add(address, 0);
+    fn rem(&mut self, address: u64) -> Result<(), ()> { // the inserted breakpoint gets its byte back first (the tracee has to be stopped), a missing one is already removed
+        let breakpoint = match self.get(&address) {
+            Some(breakpoint) => breakpoint,
+            None => return Ok(())
+        };
+        if breakpoint.inserted {
+            for pid in image_inferiors() {
+                let _ = remove_breakpoint(pid, anti_normal(address), breakpoint.byte);
+            }
+            remove_breakpoint(PID.access().unwrap(), anti_normal(address), breakpoint.byte)?;
+        }
+        self.remove(&address);
+        Ok(())
     }
 
-    fn rem(&mut self, address: u64) -> u8 {
-        self.remove(&address).unwrap()
+    fn edit(&mut self, address: u64, settings: Breakpoint) { // changes only the user settings, creating the breakpoint if needed
+        let breakpoint = self.entry(address).or_default();
+        breakpoint.condition = settings.condition;
+        breakpoint.enabled = settings.enabled;
+        breakpoint.ignore = settings.ignore;
+        breakpoint.temporary = settings.temporary;
+        breakpoint.log = settings.log;
     }
 
-    fn disable_all(&self) -> Result<(), ()> { // This doesnt actually remove the saved breakpoints, it just removes them out of the tracee's code, good for single stepping and such
-        let keys = self.keys();
-        for key in keys {
-            let byte = self.get(key).unwrap();
-            remove_breakpoint(PID.access().unwrap(), anti_normal(*key), *byte)?;
+    fn disable_all(&mut self) -> Result<(), ()> { // This doesnt actually remove the saved breakpoints, it just removes them out of the tracee's code, good for single stepping and such
+        for pid in image_inferiors() { // forked processes have the breakpoints as well
+            for (key, breakpoint) in self.iter().filter(|(_, breakpoint)| breakpoint.inserted) {
+                let _ = remove_breakpoint(pid, anti_normal(*key), breakpoint.byte);
+            }
+        }
+        for (key, breakpoint) in self.iter_mut().filter(|(_, breakpoint)| breakpoint.inserted) { // breakpoints added while running were never inserted
+            remove_breakpoint(PID.access().unwrap(), anti_normal(*key), breakpoint.byte)?;
+            breakpoint.inserted = false;
         };
         Ok(())
     }
 
-    fn enable_all(&mut self) -> Result<(), ()> { // we insert all of the breakpoints in the programs memory, and save the replaced bytes
+    fn enable_all(&mut self) -> Result<(), ()> { // we insert all of the enabled breakpoints in the programs memory, and save the replaced bytes
+        for pid in image_inferiors() { // the forked processes run the same code, so the saved bytes are the same
+            for (key, _) in self.iter().filter(|(key, breakpoint)| breakpoint.enabled && image_loaded(**key)) {
+                let _ = insert_breakpoint(pid, anti_normal(*key));
+            }
+        }
         let copy = self.clone();
-        let keys = copy.keys();
+        let keys = copy.iter().filter(|(key, breakpoint)| breakpoint.enabled && !breakpoint.inserted && image_loaded(**key)).map(|(key, _)| key); // the breakpoints of unloaded libraries stay pending
         for key in keys {
             let byte = insert_breakpoint(PID.access().unwrap(), anti_normal(*key)); // antinormal, because we are only saving the normalized values in the BREAKPOINTS addresses
             match byte {
-                Ok(byte) => *self.get_mut(key).unwrap() = byte,
+                Ok(byte) => self.add(*key, byte),
                 Err(()) => {self.remove(key);}
             };
         };
@@ -85,6 +191,191 @@ impl ImplBreakpoints for Breakpoints {
     }
 }
 
+// Hardware watchpoints, each one is in one of the DR0-DR3 debug registers of every thread
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchKind {
+    Write,
+    ReadWrite,
+    Execute
+}
+
+impl std::fmt::Display for WatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            WatchKind::Write => "Write",
+            WatchKind::ReadWrite => "Read/Write",
+            WatchKind::Execute => "Execute"
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Watchpoint {
+    pub address: u64, // real address (the data isnt shifted like the code)
+    pub size: u8, // 1, 2, 4 or 8 bytes, aligned
+    pub kind: WatchKind,
+    pub name: Option<String>, // the variable it was created from
+    pub value: Vec<u8> // last known value, to show the change
+}
+
+impl Watchpoint {
+    pub fn new(address: u64, size: u8, kind: WatchKind, name: Option<String>) -> Self {
+        Watchpoint {
+            address,
+            size: if kind == WatchKind::Execute {1} else {size}, // instructions use the length of 1
+            kind,
+            name,
+            value: Vec::new()
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} (0x{:x})", name, self.address),
+            None => format!("0x{:x}", self.address)
+        }
+    }
+}
+
+pub type Watchpoints = [Option<Watchpoint>; 4];
+
+// Source steps that stay in a function call, the stops of their temporary breakpoints get checked against the frame
+#[derive(Debug, Clone)]
+pub enum StepScope {
+    Over(StepFrame), // next line of the same call, callees and recursive calls run through
+    Out(StepFrame) // return to the caller
+}
+
+// The rendezvous with the dynamic linker, it calls _dl_debug_state after every change of the loaded libraries
+#[derive(Debug, Clone)]
+pub struct Linker {
+    pub r_debug: u64, // real address of the r_debug structure (its r_map is the list of the libraries)
+    pub address: u64, // real address of _dl_debug_state, our hidden breakpoint
+    pub byte: Option<u8> // the replaced byte while the breakpoint is inserted
+}
+
+// The x87 and vector registers of a thread (the user_regs_struct has only the general ones)
+#[derive(Debug, Clone)]
+pub struct FpRegisters {
+    pub st: [[u8; 10]; 8], // 80 bit extended precision
+    pub mxcsr: u32,
+    pub xmm: [[u8; 16]; 16],
+    pub ymm: Option<[[u8; 16]; 16]> // the upper halves of the YMM registers, only with AVX
+}
+
+// A copy of the stopped tracee (forked by an injected syscall), kept stopped until it gets restored
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub pid: Pid,
+    pub label: String // where the tracee was stopped
+}
+
+// A function called from an expression, the arguments already sorted into the registers and the stack
+#[derive(Debug, Clone, Default)]
+pub struct Call {
+    pub address: u64,
+    pub integers: Vec<u64>,
+    pub floats: Vec<u64>, // the low halves of the xmm registers
+    pub stack: Vec<u64>, // the arguments that did not fit, in order
+    pub returns: CallReturn
+}
+
+impl Call {
+    pub fn integer_registers(&self) -> usize { // the address of a struct result takes the first one
+        CALL_INTEGERS - matches!(self.returns, CallReturn::Memory(_)) as usize
+    }
+
+    pub fn integer(&mut self, value: u64) { // the 7th and later go on the stack
+        if self.integers.len() < self.integer_registers() {
+            self.integers.push(value);
+        } else {
+            self.stack.push(value);
+        }
+    }
+
+    pub fn float(&mut self, value: u64) { // the 9th and later go on the stack
+        if self.floats.len() < CALL_FLOATS {
+            self.floats.push(value);
+        } else {
+            self.stack.push(value);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum CallReturn {
+    #[default]
+    Registers, // scalars in rax or xmm0
+    Struct(u64, Vec<bool>), // small structs in rax, rdx, xmm0 and xmm1, the eightbytes that are floats (sse)
+    Memory(u64) // bigger structs in the memory, its address is passed as the first integer
+}
+
+#[derive(Debug, Clone)]
+pub struct CallResult {
+    pub rax: u64,
+    pub xmm0: [u8; 16],
+    pub memory: u64 // the struct results, below the stack of the tracee
+}
+
+// Post-mortem debugging of an ELF core dump, it takes the place of the proc_fs (memory, maps and registers), read only
+pub struct Core {
+    pub path: PathBuf,
+    pub pid: Pid,
+    pub signal: Option<Signal>, // the signal that killed the process
+    pub threads: Vec<(Pid, user_regs_struct)>, // NT_PRSTATUS, the thread that crashed is the first
+    pub fpregs: HashMap<Pid, FpRegisters>, // NT_FPREGSET and NT_X86_XSTATE, they follow the NT_PRSTATUS of their thread
+    auxv: Vec<u8>, // NT_AUXV
+    segments: Vec<CoreSegment>, // PT_LOAD
+    files: Vec<(std::ops::Range<u64>, u64, String)>, // NT_FILE, the mapped range with its offset into the file
+    data: Vec<u8>
+}
+
+struct CoreSegment {
+    range: std::ops::Range<u64>, // memory address range
+    offset: u64, // into the core file
+    size: u64, // bytes dumped, the rest is read from the mapped file (the code is usually not dumped)
+    flags: u32
+}
+
+// Threads of the tracee, each thread is traced on its own (but they share the memory and breakpoints)
+#[derive(Debug, Clone)]
+pub struct Thread {
+    pub process: Pid, // thread group (the inferior) the thread belongs to
+    pub regs: Option<user_regs_struct>, // registers from the last stop
+    pub name: String, // from the proc_fs (comm)
+    pub function: Option<String>, // function at the last stop
+    pub running: bool,
+    stepping: bool, // single stepped, events in the middle of the step continue stepping
+    rewound: bool, // hit a breakpoint while we were stopping it, so we moved it back to hit it again after continue
+    stop_pending: bool, // our SIGSTOP wasnt consumed yet, we swallow it later
+    signal: Option<Signal> // signal that arrived while we were stopping the thread, delivered on continue
+}
+
+impl Thread {
+    fn new(process: Pid) -> Self {
+        Thread {
+            process,
+            regs: None,
+            name: String::new(),
+            function: None,
+            running: false,
+            stepping: false,
+            rewound: false,
+            stop_pending: false,
+            signal: None
+        }
+    }
+
+    fn update(&mut self, tid: Pid) { // reloading the info of a stopped thread
+        self.regs = ptrace::getregs(tid).ok();
+        self.function = self.regs.and_then(|regs| function_name(regs.rip));
+        self.name = std::fs::read_to_string(format!("/proc/{}/task/{tid}/comm", self.process)).map(|name| name.trim_end().to_string()).unwrap_or_default();
+    }
+}
+
+pub type Threads = BTreeMap<Pid, Thread>;
+pub type Inferiors = BTreeMap<Pid, PathBuf>; // traced processes (more than one only when following forks) with their executables
+
 // Program Memory Maps
 #[derive(Debug)]
 pub struct MapBits {
@@ -105,27 +396,55 @@ pub struct MemoryMap {
 #[derive(Debug, Clone)]
 pub enum Operation {
     LoadFile,
+    LoadCore,
+    CloseCore,
     RunTracee,
     StopTracee,
     Step,
     SourceStep,
+    StepOver,
+    StepOut,
+    RunTo(u64),
+    Jump(u64),
     Pause,
     Continue,
     Kill,
     Signal(Signal),
+    Attach(i32),
+    SelectThread(Pid),
     BreakpointAdd(u64),
+    BreakpointFunction(Vec<u64>, String),
     BreakpointRemove(u64),
+    BreakpointEdit(u64, Breakpoint),
+    WatchpointAdd(Watchpoint),
+    WatchpointRemove(usize),
+    MemoryWrite(u64, Vec<u8>),
+    RegistersEdit(user_regs_struct),
+    SyscallLog(bool),
+    SyscallClear,
+    CatchpointAdd(syscall::Catchpoint),
+    CatchpointRemove(usize),
+    LogClear,
+    WatchAdd(String),
+    WatchRemove(usize),
+    Checkpoint,
+    CheckpointRestore(usize),
+    CheckpointRemove(usize),
+    Record(bool),
+    ReverseStep,
+    ReverseSourceStep,
+    ReverseContinue,
     HandleSignal(Result<wait::WaitStatus, nix::errno::Errno>),
     Reset,
     _ResetFile,
     Read(Result<(Vec<u8>, usize), ()>),
-    Stack(Result<Vec<(usize, String)>, ()>)
+    Stack(Result<Vec<StackLine>, ()>)
 }
 
 // Tasks definition (to perform async)
 
 fn task_wait() -> iced::Task<window::Message> { // task_wait creates an async thread to wait for the tracee to stop (by signal, breakpoint or user)
-    iced::Task::perform(wait_async(PID.access().unwrap()), |result| window::Message::Operation(Operation::HandleSignal(result)))
+    iced::Task::perform(wait_async(Pid::from_raw(-1)), |result| window::Message::Operation(Operation::HandleSignal(result))) // any of the threads can stop
 }
 
 fn task_reset() -> iced::Task<window::Message> {
@@ -167,37 +486,41 @@ fn task_stack_update(id: iced::widget::pane_grid::Pane) -> iced::Task<window::Me
 pub fn operation_message(state: &mut window::State, operation: Operation, task: &mut Option<iced::Task<window::Message>>) {
     match operation {
         Operation::LoadFile => {
-            let reset = FILE.access().is_some();
-
             let file = match Dialog::file(None, None) { // user file selection
                 Some(file) => file,
                 None => return
             };
-            if reset { // if a different file was selected, then we clear the Globals
-                if reset_file(state).is_err() {
-                    return;
-                };
+            let _ = load_file(state, file, task);
+        },
+        Operation::LoadCore => {
+            if PID.access().is_some() {
+                return;
             }
-
-            match object::test_file(&file) { // we test the file, if its an executable
-                Ok(_) => (),
-                Err(()) => return
+            let file = match Dialog::core_file() {
+                Some(file) => file,
+                None => return
             };
-
-            let data = object::read_file(&file); // we read the file contents and set the data to the GLOBAL
-            unsafe {
-                DATA = data
+            let core = match Core::parse(file) {
+                Ok(core) => core,
+                Err(err) => {Dialog::error(err, Some("Core Error")); return;}
+            };
+            if FILE.access().is_none() { // the executable of the core, unless the user selected its copy already
+                let path = match core.executable() {
+                    Some(path) => PathBuf::from(path),
+                    None => {Dialog::error("The executable of the core dump was not found, load it first.", Some("Core Error")); return;}
+                };
+                if load_file(state, path, task).is_err() {
+                    return;
+                }
             }
-            FILE.sets(file.clone()); // setting the path to the new file
-
-            let no_debug = dwarf_set(state).is_err(); // preloading all dwarf related data, err when no debug information
-            state.internal.no_debug = no_debug;
-            if no_debug {
-                Dialog::warning(&format!("This file ({}) does not contain debbuging data.", file.file_name().unwrap().to_str().unwrap()), None);
-                BREAKPOINTS.sets(Breakpoints::new());
-                return;
+            let mut setup = None;
+            core_setup(state, core, &mut setup);
+            *task = batch_tasks(task.take(), setup); // along with the pane preload of the file
+        },
+        Operation::CloseCore => {
+            if CORE.access().is_some() {
+                *task = Some(task_reset());
             }
-            panes_preload(state, task); // preloading the panes (code widgets with the main file of the binary)
         },
 
         Operation::RunTracee => {
@@ -206,18 +529,74 @@ pub fn operation_message(state: &mut window::State, operation: Operation, task:
                 Err(()) => return
             };
 
-            let pid = match object::run_tracee(FILE.access().as_ref().unwrap(), Vec::new(), Some(stdio)) {
+            let launch = LAUNCH.access().clone().unwrap(); // launch configuration (args, env, working dir)
+            let stop_at = launch.stop_at.unwrap_or(config::StopAt::none);
+            let pid = match object::run_tracee(FILE.access().as_ref().unwrap(), launch, Some(stdio)) {
                 Err(_) => return,
                 Ok(pid) => {PID.sets(Pid::from_raw(pid)); Pid::from_raw(pid)}, // we save the tracee pid to the global
             };
             tracee_setup(state, pid, task); // we setup the tracee data
 
             *task = Some(task_read()); // we launch the reading from the PTY
+            let stop = match stop_at { // a temporary breakpoint, the tracee continues to it right away
+                config::StopAt::none => None,
+                config::StopAt::entry => object::entry_point(),
+                config::StopAt::main => main_breakpoint().map(|(address, _)| address)
+            };
+            match stop {
+                Some(address) => *task = batch_tasks(task.take(), Some(iced::Task::done(window::Message::Operation(Operation::RunTo(address))))),
+                None if stop_at != config::StopAt::none => Dialog::warning(&format!("The {} of the program was not found, it stays stopped after the launch.", stop_at.to_string().to_lowercase()), None),
+                None => ()
+            }
+        },
+        Operation::Attach(pid) => {
+            if PID.access().is_some() {
+                return;
+            };
+            let pid = Pid::from_raw(pid);
+
+            let path = match get_tracee_path(&PathBuf::from(format!("/proc/{pid}/"))) { // the executable of the running process
+                Ok(path) => path,
+                Err(()) => return
+            };
+            if FILE.access().as_ref() != Some(&path) && load_file(state, path, task).is_err() { // we load its debug data, unless we already have it
+                return;
+            }
+
+            if attach_tracee(pid).is_err() {
+                return;
+            };
+            PID.sets(pid);
+            state.internal.attached = true;
+
+            let mut setup = None;
+            tracee_setup(state, pid, &mut setup); // the attach stops the tracee with SIGSTOP, which tracee_setup waits for
+            attach_threads(pid);
+            *task = batch_tasks(task.take(), setup); // we keep the pane preload of the file along with the setup
         },
         Operation::StopTracee => {
             if PID.access().is_none() {
                 return;
             };
+            if state.internal.attached { // we never kill a process we attached to, we just let it go
+                if !state.internal.stopped { // detaching requires a stopped tracee, so we stop it and detach in the handle
+                    let leader = PID.access().unwrap();
+                    if stop_thread(leader, leader).is_err() { // a SIGSTOP to the leader only, consumed before we leave (a SIGTRAP would outlive the detach)
+                        return;
+                    }
+                    if let Some(thread) = THREADS.access().as_mut().and_then(|threads| threads.get_mut(&leader)) {
+                        thread.stop_pending = true;
+                    }
+                    state.internal.detach = true;
+                    state.internal.manual = true;
+                    return;
+                }
+                if detach_tracee(PID.access().unwrap()).is_err() {
+                    return;
+                };
+                *task = Some(task_reset());
+                return;
+            }
             match kill_tracee(PID.access().unwrap()) {
                 Ok(_) => (),
                 Err(()) => return
@@ -226,13 +605,25 @@ pub fn operation_message(state: &mut window::State, operation: Operation, task:
             *task = Some(task_reset()); // we reset the Trace data and globals
         },
 
-        Operation::Step => {
-            if step(PID.access().unwrap(), state.last_signal).is_err() {return;};
+        Operation::Step => { // steps only the selected thread, the others stay stopped
+            let tid = current_thread();
+            let signal = if Some(tid) == state.internal.stopped_thread {state.last_signal} else {None}; // the signal belongs to the thread that stopped
+            if recording() && record_step(tid).is_err() {return;}
+            if step(tid, signal).is_err() {return;};
+            set_running(tid, true);
             state_cont(state);
             *task = Some(task_wait())
         },
         Operation::SourceStep => {
-            let pid = PID.access().unwrap();
+            let pid = current_thread(); // like step, only the selected thread runs
+            if recording() { // instruction by instruction until a line, the handle keeps stepping
+                if record_step(pid).is_err() || step(pid, None).is_err() {return;}
+                state.internal.record_steps = Some(1);
+                set_running(pid, true);
+                state_cont(state);
+                *task = Some(task_wait());
+                return;
+            }
             if step(pid, None).is_err() {return;}; // we step away from the last line
             let _ = wait(pid);
             let mut breakpoints = Breakpoints::new(); // we create temporary breakpoints, where all addresses in the LINES get a breakpoint
@@ -245,11 +636,56 @@ pub fn operation_message(state: &mut window::State, operation: Operation, task:
                 breakpoints.disable_all().unwrap();
                 return;
             };
+            set_running(pid, false);
             state.internal.source_step = Some(breakpoints); // we save the breakpoints
             state.internal.stopped = false;
             state_cont(state); // we set the state to running
             *task = Some(task_wait()) // and wait for the next stop
         },
+        Operation::StepOver => {
+            let pid = current_thread();
+            if let Ok(frame) = current_step_frame(pid) {
+                scope_step(state, pid, StepScope::Over(frame), task);
+            }
+        },
+        Operation::StepOut => {
+            let pid = current_thread();
+            if let Ok(frame) = current_step_frame(pid) {
+                scope_step(state, pid, StepScope::Out(frame), task);
+            }
+        },
+        Operation::RunTo(addr) => { // continues to the line with a temporary breakpoint, unless the line already has a breakpoint
+            if !state.internal.stopped {
+                return;
+            }
+            BREAKPOINTS.access().as_mut().unwrap().entry(addr).or_insert_with(|| Breakpoint {temporary: true, ..Default::default()});
+            operation_message(state, Operation::Continue, task);
+        },
+        Operation::Jump(addr) => { // moves the rip of the selected thread, the code in between is not executed
+            if !state.internal.stopped {
+                return;
+            }
+            let tid = current_thread();
+            let mut regs = match *REGISTERS.access() {
+                Some(regs) => regs,
+                None => return
+            };
+            let target = anti_normal(addr);
+            if function_name(target) != function_name(regs.rip) && Dialog::warning_choice("The line is in a different function, its stack frame will not be set up.\nDo you wish to jump anyway?", Some("Jump")) != rfd::MessageDialogResult::Yes {
+                return;
+            }
+            regs.rip = target;
+            if set_registers(tid, regs).is_err() {
+                return;
+            }
+            forget_history(); // the recorded states dont lead here anymore
+            REGISTERS.sets(regs);
+            if let Some(thread) = THREADS.access().as_mut().unwrap().get_mut(&tid) {
+                thread.regs = Some(regs);
+            }
+            state.internal.breakpoint = false; // we are not on the breakpoint anymore
+            refresh(state, regs.rip, task);
+        },
         Operation::Pause => {
             if send_signal(PID.access().unwrap(), Signal::SIGTRAP).is_err() { // we manually stop the program (as if it hit a breakpoint)
                 return;
@@ -257,19 +693,23 @@ pub fn operation_message(state: &mut window::State, operation: Operation, task:
             state.internal.manual = true; // we set the manual so we know the program stopped because of us
         },
         Operation::Continue => {
-            let pid = PID.access().unwrap();
-            if step(pid, None).is_err() {return;}; // we step away from the current line (as to not hit the same breakpoint again)
-            let _ = wait(pid);
+            let pid = state.internal.stopped_thread.unwrap_or(PID.access().unwrap());
+            forget_history(); // the tracee runs without recording
+            step_threads(); // we step away from the breakpoints (as to not hit the same breakpoint again)
             if BREAKPOINTS.access().as_mut().unwrap().enable_all().is_err() { // we enable all of the breakpoints
                 *task = Some(task_reset()); // if that fails we reset the file
                 return;
             };
+            linker_insert(); // and follow the library loads
             if restart_tracee(pid, state.last_signal).is_err() { // and we continue the tracee
+                linker_remove();
                 if BREAKPOINTS.access().as_mut().unwrap().disable_all().is_err() {
                     *task = Some(task_reset())
                 };
                 return;
             };
+            set_running(pid, false);
+            resume_threads(pid); // and all of the other threads
 
             *task = Some(task_wait()); // we wait for the next stop
 
@@ -301,18 +741,216 @@ pub fn operation_message(state: &mut window::State, operation: Operation, task:
             let _ = signal_tracee(pid, sig);
         },
 
+        Operation::SelectThread(tid) => { // switching the thread shown in the Registers, Stack, Code and Assembly panes
+            let core = CORE.access().is_some();
+            if !state.internal.stopped && !core {
+                return;
+            }
+            let regs = match THREADS.access().as_ref().and_then(|threads| threads.get(&tid).and_then(|thread| thread.regs)) {
+                Some(regs) => regs,
+                None => return
+            };
+            THREAD.sets(tid);
+            REGISTERS.sets(regs);
+            if core { // a core has only one process
+                *FPREGS.access() = CORE.access().as_ref().and_then(|core| core.fpregs.get(&tid).cloned());
+            } else {
+                *FPREGS.access() = get_fp_registers(tid).ok();
+                focus(state, thread_process(tid)); // the thread might belong to another inferior
+            }
+            refresh(state, regs.rip, task);
+        },
+
         Operation::BreakpointAdd(addr) => BREAKPOINTS.access().as_mut().unwrap().add_future(addr),
-        Operation::BreakpointRemove(addr) => {BREAKPOINTS.access().as_mut().unwrap().rem(addr);},
+        Operation::BreakpointFunction(addrs, name) => { // on every function with the name
+            let mut bind = BREAKPOINTS.access();
+            let breakpoints = bind.as_mut().unwrap();
+            for addr in addrs {
+                breakpoints.add_future(addr);
+                breakpoints.get_mut(&addr).unwrap().function = Some(name.clone());
+            }
+        },
+        Operation::BreakpointRemove(addr) => {
+            let mut bind = BREAKPOINTS.access();
+            let breakpoints = bind.as_mut().unwrap();
+            if !state.internal.stopped && breakpoints.get(&addr).is_some_and(|breakpoint| breakpoint.inserted) { // the code of a running tracee cant be written, the 0xCC would stay there
+                drop(bind);
+                Dialog::error("Breakpoints can be removed only while the program is stopped.", Some("Breakpoint Error"));
+                return;
+            }
+            let _ = breakpoints.rem(addr);
+        },
+        Operation::BreakpointEdit(addr, settings) => BREAKPOINTS.access().as_mut().unwrap().edit(addr, settings), // the condition was already checked for syntax errors
+
+        Operation::WatchpointAdd(watchpoint) => { // the debug registers can be written only while the threads are stopped
+            if !state.internal.stopped {
+                Dialog::error("Watchpoints can be set only while the program is stopped.", Some("Watchpoint Error"));
+                return;
+            }
+            if let Err(err) = add_watchpoint(watchpoint) {
+                Dialog::error(err, Some("Watchpoint Error"));
+            }
+        },
+        Operation::WatchpointRemove(index) => {
+            if !state.internal.stopped {
+                return;
+            }
+            if let Some(watchpoints) = WATCHPOINTS.access().as_mut() {
+                watchpoints[index] = None;
+            }
+            for tid in watch_threads() {
+                let _ = apply_watchpoints(tid);
+            }
+        },
+
+        Operation::MemoryWrite(address, bytes) => if write_memory(state, address, bytes).is_ok() {
+            forget_history();
+        },
+        Operation::RegistersEdit(regs) => { // of the selected thread, the panes get updated like after a stop
+            if !state.internal.stopped || CORE.access().is_some() {
+                return;
+            }
+            let tid = current_thread();
+            let old = *REGISTERS.access();
+            if set_registers(tid, regs).is_err() {
+                return;
+            }
+            forget_history();
+            REGISTERS.sets(regs);
+            if let Some(thread) = THREADS.access().as_mut().unwrap().get_mut(&tid) {
+                thread.regs = Some(regs);
+                thread.function = function_name(regs.rip);
+            }
+            if old.is_some_and(|old| old.rip != regs.rip) { // we moved away from the breakpoint
+                state.internal.breakpoint = false;
+            }
+            refresh(state, regs.rip, task);
+        },
+
+        Operation::SyscallLog(log) => SYSCALLS.access().get_or_insert_default().log = log, // the running threads switch on their next stop
+        Operation::SyscallClear => state.internal.pane.syscalls.clear(),
+        Operation::CatchpointAdd(catchpoint) => {
+            let mut bind = SYSCALLS.access();
+            let catchpoints = &mut bind.get_or_insert_default().catchpoints;
+            if !catchpoints.contains(&catchpoint) {
+                catchpoints.push(catchpoint);
+            }
+        },
+        Operation::CatchpointRemove(index) => {
+            if let Some(tracing) = SYSCALLS.access().as_mut() && index < tracing.catchpoints.len() {
+                tracing.catchpoints.remove(index);
+            }
+        },
+
+        Operation::LogClear => state.internal.pane.logs.clear(),
+        Operation::WatchAdd(expression) => {
+            let value = if state.internal.stopped {watch_value(&expression)} else {String::new()};
+            state.internal.pane.watches.push((expression, value));
+        },
+        Operation::WatchRemove(index) => {
+            if index < state.internal.pane.watches.len() {
+                state.internal.pane.watches.remove(index);
+            }
+        },
+
+        Operation::Checkpoint => {
+            if !state.internal.stopped || CORE.access().is_some() {
+                return;
+            }
+            if state.internal.attached { // restoring kills the current process
+                Dialog::error("Checkpoints cannot be made in an attached process.", Some("Checkpoint Error"));
+                return;
+            }
+            if THREADS.access().as_ref().is_some_and(|threads| threads.len() > 1) { // the fork copies only the calling thread
+                Dialog::error("Checkpoints work only with single threaded programs.", Some("Checkpoint Error"));
+                return;
+            }
+            let rip = REGISTERS.access().unwrap().rip;
+            let label = match (function_name(rip), &state.internal.pane.file) {
+                (Some(function), Some(index)) => format!("{} line {}", function, index.line),
+                (Some(function), None) => format!("{} 0x{:x}", function, rip),
+                (None, _) => format!("0x{:x}", rip)
+            };
+            match fork_process(PID.access().unwrap()) {
+                Ok(pid) => CHECKPOINTS.access().get_or_insert_default().push(Checkpoint {pid, label}),
+                Err(()) => Dialog::error("Could not fork the program for the checkpoint.", Some("Checkpoint Error"))
+            }
+        },
+        Operation::CheckpointRestore(index) => {
+            if !state.internal.stopped {
+                return;
+            }
+            let checkpoint = match CHECKPOINTS.access().as_ref().and_then(|checkpoints| checkpoints.get(index).cloned()) {
+                Some(checkpoint) => checkpoint,
+                None => return
+            };
+            match fork_process(checkpoint.pid) { // the checkpoint stays for the next restore, its copy becomes the tracee
+                Ok(copy) => CHECKPOINTS.access().as_mut().unwrap()[index].pid = copy,
+                Err(()) => {CHECKPOINTS.access().as_mut().unwrap().remove(index);}
+            }
+
+            let inferiors: Vec<Pid> = INFERIORS.access().as_ref().map(|inferiors| inferiors.keys().copied().collect()).unwrap_or_default();
+            for pid in inferiors { // the current processes end, nothing is waiting for them while stopped
+                if ptrace::kill(pid).is_ok() {
+                    while matches!(wait(pid), Ok(status) if !matches!(status, wait::WaitStatus::Exited(..) | wait::WaitStatus::Signaled(..))) {}
+                }
+            }
+            close_memory();
+            INFERIORS.none();
+            WATCHPOINTS.none(); // the debug registers are not inherited
+            forget_history();
+
+            PID.sets(checkpoint.pid);
+            if send_signal(checkpoint.pid, Signal::SIGSTOP).is_err() || ptrace::cont(checkpoint.pid, None).is_err() { // a new stop for the tracee_setup, the signal comes before any instruction
+                *task = Some(task_reset());
+                return;
+            }
+            state.internal.breakpoint = false;
+            state.internal.watchpoint = None;
+            state.internal.returned = None;
+            state.internal.catchpoint = None;
+            state.internal.reversed = None;
+            state.last_signal = None;
+            let mut setup = None;
+            tracee_setup(state, checkpoint.pid, &mut setup);
+            let rip = REGISTERS.access().unwrap().rip;
+            refresh(state, rip, task);
+            *task = batch_tasks(setup, task.take());
+        },
+        Operation::CheckpointRemove(index) => {
+            let mut bind = CHECKPOINTS.access();
+            if let Some(checkpoints) = bind.as_mut() && index < checkpoints.len() {
+                let _ = ptrace::detach(checkpoints.remove(index).pid, Signal::SIGKILL); // killed after we let go, so its parent reaps it
+            }
+        },
+        Operation::Record(on) => {
+            RECORD.access().get_or_insert_default().on = on;
+            if !on {
+                forget_history();
+            }
+        },
+        Operation::ReverseStep => reverse(state, |_| true, task),
+        Operation::ReverseSourceStep => reverse(state, |rip| LINES.access().as_ref().is_some_and(|lines| lines.get_line(rip).is_some()), task), // back to the start of a line
+        Operation::ReverseContinue => reverse(state, |rip| BREAKPOINTS.access().as_ref().is_some_and(|breakpoints| breakpoints.get(&normal(rip)).is_some_and(|breakpoint| breakpoint.enabled)), task), // the conditions are not evaluated in the past
 
         Operation::HandleSignal(Ok(status)) => handle(state, status, task),
         Operation::HandleSignal(Err(err)) => *task = match Dialog::warning_choice(&format!("Encountered an error while waiting for the tracee program: {}\nDo you wish to try again? (selecting no will kill the tracee)", err), Some("Trace Error")) {
-            rfd::MessageDialogResult::Yes => Some(iced::Task::perform(wait_async(PID.access().unwrap()), |result| window::Message::Operation(Operation::HandleSignal(result)))),
+            rfd::MessageDialogResult::Yes => Some(task_wait()),
             _ => Some(iced::Task::done(window::Message::Operation(Operation::StopTracee)))
         }, // if we encounter an error while waiting for the tracee to stop, we give the user an option to try again
 
         Operation::Reset => { // tracee state reset
             state.internal.stopped = false;
+            state.internal.attached = false;
+            state.internal.detach = false;
+            state.internal.stopped_thread = None;
+            state.internal.watchpoint = None;
+            state.internal.returned = None;
+            state.internal.catchpoint = None;
             state.internal.source_step = None;
+            state.internal.step_scope = None;
+            state.internal.record_steps = None;
+            state.internal.reversed = None;
             state.internal.manual = false;
             state.internal.breakpoint = false;
             state.internal.pane.file = None;
@@ -366,6 +1004,34 @@ pub fn operation_message(state: &mut window::State, operation: Operation, task:
 
 // setup functions
 
+fn load_file(state: &mut window::State, file: PathBuf, task: &mut Option<iced::Task<window::Message>>) -> Result<(), ()> { // selecting a new executable (from the file dialog or from an attached process)
+    if FILE.access().is_some() { // if a different file was selected, then we clear the Globals
+        reset_file(state)?;
+    }
+
+    object::test_file(&file)?; // we test the file, if its an executable
+
+    let data = object::read_file(&file); // we read the file contents and set the data to the GLOBAL
+    unsafe {
+        DATA = data
+    }
+    FILE.sets(file.clone()); // setting the path to the new file
+
+    let session = session::load(&file).unwrap_or_default(); // the layout first, the panes get preloaded with the file
+    session::apply(state, &session);
+
+    let no_debug = dwarf_set(state).is_err(); // preloading all dwarf related data, err when no debug information
+    state.internal.no_debug = no_debug;
+    if no_debug {
+        Dialog::warning(&format!("This file ({}) does not contain debbuging data.", file.file_name().unwrap().to_str().unwrap()), None);
+        BREAKPOINTS.sets(Breakpoints::new());
+        return Ok(());
+    }
+    panes_preload(state, task); // preloading the panes (code widgets with the main file of the binary)
+    session::restore_breakpoints(state);
+    Ok(())
+}
+
 fn dwarf_set(state: &mut window::State) -> Result<(), ()> {
     #[allow(static_mut_refs)]
     let data = unsafe { // static reference
@@ -395,8 +1061,8 @@ fn dwarf_set(state: &mut window::State) -> Result<(), ()> {
     EHFRAME.sets(EhFrame::new(object));
 
 
-    load_source(dwarf.dwarf(endian));
-    parse_functions(dwarf.dwarf(endian));
+    load_source(dwarf.dwarf(endian), 0);
+    parse_functions(dwarf.dwarf(endian), 0);
 
     DWARF.sets(dwarf);
     Ok(())
@@ -423,30 +1089,27 @@ fn panes_preload(state: &mut window::State, task: &mut Option<iced::Task<window:
 }
 
 fn tracee_setup(state: &mut window::State, pid: Pid, task: &mut Option<iced::Task<window::Message>>) {
-    let proc_path = PathBuf::from(format!("/proc/{pid}/"));
     state.status = Some(wait(pid).unwrap());
-    let path = get_tracee_path(&proc_path).unwrap();
-    FILE.sets(path.clone());
-
-    let maps = get_process_maps(&proc_path).unwrap();
-
-    for map in &maps { // we find our exec shift, unless its a static executable
-        if map.name != path.to_str().unwrap() {
-            continue;
-        }
-        if map.offset == 0 {
-            if state.internal.static_exec {break;}
-            EXEC_SHIFT.sets(map.range.start);
-            break;
-        }
-    };
-
-    MAPS.sets(maps);
-    MEMORY.sets(open_memory(&proc_path).unwrap());
-    PROC_PATH.sets(proc_path);
+    let _ = set_options(pid); // from now on we follow the threads and forks created by the tracee
+    if process_setup(state, pid).is_err() {
+        return;
+    }
+    linker_setup(&process_auxv(pid));
+    sync_libraries(pid); // an attached process has them loaded already
+    FILE.sets(INFERIORS.access().as_ref().unwrap()[&pid].clone());
     state.internal.stopped = true;
 
-    REGISTERS.sets(get_registers(pid).unwrap());
+    let regs = get_registers(pid).unwrap();
+    REGISTERS.sets(regs);
+    *FPREGS.access() = get_fp_registers(pid).ok();
+    let mut thread = Thread::new(pid);
+    thread.update(pid);
+    THREADS.sets(Threads::from([(pid, thread)]));
+    THREAD.sets(pid);
+    state.internal.stopped_thread = Some(pid);
+    if let Some(breakpoints) = BREAKPOINTS.access().as_mut() { // counting from the start of this run
+        breakpoints.values_mut().for_each(|breakpoint| breakpoint.hits = 0);
+    }
 
     let panes = &mut state.layout.panes;
 
@@ -469,7 +1132,44 @@ fn tracee_setup(state: &mut window::State, pid: Pid, task: &mut Option<iced::Tas
 
 fn handle(state: &mut window::State, status: wait::WaitStatus, task: &mut Option<iced::Task<window::Message>>) { // handling signals
     state.status = Some(status);
+    state.internal.catchpoint = None;
+    state.internal.reversed = None;
+    let leader = PID.access().unwrap();
+    if let Some(steps) = state.internal.record_steps.take() && let wait::WaitStatus::Stopped(tid, Signal::SIGTRAP) = status { // a recorded source step continues until a line (or the history fills up)
+        let line = get_registers(tid).is_ok_and(|regs| LINES.access().as_ref().unwrap().get_line(regs.rip).is_some());
+        if !line && steps < record::history_limit() && record_step(tid).is_ok() && step(tid, None).is_ok() {
+            state.internal.record_steps = Some(steps + 1);
+            *task = Some(task_wait());
+            return;
+        }
+    }
     match status {
+        wait::WaitStatus::PtraceSyscall(tid) => { // syscall entry or exit, the tracee stops only on a catchpoint
+            state.internal.catchpoint = syscall_stop(state, tid);
+            if state.internal.catchpoint.is_none() {
+                resume_thread(tid);
+                *task = Some(task_wait());
+                return;
+            }
+        },
+        wait::WaitStatus::Exited(tid, _) | wait::WaitStatus::Signaled(tid, _, _) if tid != leader => { // only a thread (or another inferior) ended, the process keeps running
+            if let Some(threads) = THREADS.access().as_mut() {
+                threads.remove(&tid);
+            }
+            if let Some(inferiors) = INFERIORS.access().as_mut() {
+                inferiors.remove(&tid);
+            }
+            *task = Some(task_wait());
+            return;
+        },
+        wait::WaitStatus::Exited(pid, _) | wait::WaitStatus::Signaled(pid, _, _) if INFERIORS.access().as_ref().is_some_and(|inferiors| inferiors.len() > 1) && !test_pid(pid) => { // the other inferiors keep running
+            INFERIORS.access().as_mut().unwrap().remove(&pid);
+            THREADS.access().as_mut().unwrap().remove(&pid);
+            let next = *INFERIORS.access().as_ref().unwrap().keys().next().unwrap();
+            focus(state, next);
+            *task = Some(task_wait());
+            return;
+        },
         wait::WaitStatus::Exited(pid, exit) => {
             *task = Some(iced::Task::done(window::Message::Operation(Operation::Reset)));
             Dialog::info(&format!("Program exited with the code: {:-}.\nPid: {}", exit, pid), Some("Program exited"));
@@ -485,10 +1185,57 @@ fn handle(state: &mut window::State, status: wait::WaitStatus, task: &mut Option
                 state.last_signal = Some(signal)
             }
         },
+        wait::WaitStatus::PtraceEvent(tid, _, event) if event == ptrace::Event::PTRACE_EVENT_CLONE as i32 => { // new thread, not a stop for the user
+            thread_clone(tid);
+            *task = Some(task_wait());
+            return;
+        },
+        wait::WaitStatus::PtraceEvent(tid, _, event) if event == ptrace::Event::PTRACE_EVENT_FORK as i32 || event == ptrace::Event::PTRACE_EVENT_VFORK as i32 => {
+            thread_fork(state, tid, event == ptrace::Event::PTRACE_EVENT_VFORK as i32, true);
+            *task = Some(task_wait());
+            return;
+        },
+        wait::WaitStatus::Stopped(tid, Signal::SIGSTOP) if !state.internal.detach && swallow_stop(tid) => { // our own stop of a thread
+            *task = Some(task_wait());
+            return;
+        },
+        wait::WaitStatus::Stopped(tid, signal) if !state.internal.detach => match config::disposition(signal) { // the signal table decides if the user sees it
+            config::Disposition::stop => if !state.internal.manual && signal != Signal::SIGTRAP && signal != Signal::SIGSTOP { // delivered on the next continue (or step)
+                state.last_signal = Some(signal);
+            },
+            disposition => {
+                if disposition == config::Disposition::print {
+                    log_signal(state, tid, signal);
+                }
+                pass_signal(tid, if disposition == config::Disposition::ignore {None} else {Some(signal)});
+                *task = Some(task_wait());
+                return;
+            }
+        },
         _ => ()
     };
 
-    let pid = PID.access().unwrap();
+    let pid = status.pid().unwrap_or(leader); // the thread that stopped
+    stop_threads(state, pid); // all-stop, every other thread gets stopped as well
+    state.internal.stopped_thread = Some(pid);
+    THREAD.sets(pid);
+
+    let mut preload = None;
+    if let wait::WaitStatus::PtraceEvent(_, _, event) = status && event == ptrace::Event::PTRACE_EVENT_EXEC as i32 && thread_exec(state, pid, &mut preload).is_err() { // a new program, we stop so breakpoints can be set in it
+        return;
+    }
+    focus(state, thread_process(pid)); // the panes show the process of the stopped thread
+
+    if state.internal.detach { // the user asked to detach while running, now that the tracee is stopped we can let it go
+        state.internal.detach = false;
+        release_stop(pid, status);
+        linker_remove();
+        let _ = BREAKPOINTS.access().as_mut().unwrap().disable_all();
+        if detach_tracee(leader).is_ok() {
+            *task = Some(task_reset());
+        }
+        return;
+    }
 
     let info = match get_sig_info(pid) {
         Ok(info) => info,
@@ -501,6 +1248,12 @@ fn handle(state: &mut window::State, status: wait::WaitStatus, task: &mut Option
         },
         _ => state.internal.breakpoint = false
     };
+    state.internal.watchpoint = None;
+    state.internal.returned = None;
+    if info.si_signo == Signal::SIGTRAP as i32 && info.si_code == TRAP_HWBKPT { // the trap is after the access, so there is nothing to rewind
+        state.internal.watchpoint = watchpoint_hit(pid);
+        state.last_signal = None;
+    }
 
     let mut regs = match get_registers(pid) { // new regs
         Ok(regs) => regs,
@@ -508,26 +1261,341 @@ fn handle(state: &mut window::State, status: wait::WaitStatus, task: &mut Option
     };
     if state.internal.breakpoint { // if breakpoint we have to move a byte back (because we stopped a byte further)
         regs.rip = regs.rip -1;
-        let _ = set_registers(PID.access().unwrap(), regs);
+        let _ = set_registers(pid, regs);
     }
     REGISTERS.sets(regs);
+    *FPREGS.access() = get_fp_registers(pid).ok();
+    if let Some(thread) = THREADS.access().as_mut().unwrap().get_mut(&pid) {
+        thread.regs = Some(regs);
+    }
 
     MAPS.sets(get_process_maps(PROC_PATH.access().as_ref().unwrap()).unwrap()); // new maps
 
-    let _ = match &state.internal.source_step { // if sourcestep was active, we want to disable all of the temporary
+    let source_step = state.internal.source_step.is_some();
+    let _ = match &mut state.internal.source_step { // if sourcestep was active, we want to disable all of the temporary
         Some(breakpoints) => {
             let res = breakpoints.disable_all();
             state.internal.source_step = None;
             res
         },
-        None => if !state.internal.stopped {BREAKPOINTS.access().as_mut().unwrap().disable_all()} else {Ok(())} // otherwise disable the normal breakpoints, but only if we ever ran the program (not stepping)
+        None => if !state.internal.stopped { // otherwise disable the normal breakpoints, but only if we ever ran the program (not stepping)
+            linker_remove();
+            BREAKPOINTS.access().as_mut().unwrap().disable_all()
+        } else {Ok(())}
     };
     state.internal.stopped = true;
 
+    sync_libraries(pid); // the libraries could change during any run (or a step over dlopen)
+    if state.internal.breakpoint && !source_step && LINKER.access().as_ref().is_some_and(|linker| linker.address == regs.rip) { // the stop of our linker hook, the breakpoints of the new libraries get inserted on the continue
+        operation_message(state, Operation::Continue, task);
+        return;
+    }
+
+    if let Some(scope) = state.internal.step_scope.take() && state.internal.breakpoint { // a stop of next or finish, recursive calls can hit the breakpoints too
+        if !scope_reached(&scope, &regs) {
+            scope_step(state, pid, scope, task);
+            return;
+        }
+        if let StepScope::Out(frame) = scope {
+            state.internal.returned = Some(match frame.return_type {
+                Some(vtype) => format!("Returned from {}: {}", frame.name, return_value(frame.image, vtype, get_xmm0(pid))),
+                None => format!("Returned from {}", frame.name)
+            });
+        }
+    }
+
+    if state.internal.breakpoint && !source_step && !breakpoint_stop(state, pid, normal(regs.rip)) { // the condition is false (or the hit is ignored, or logged), so the tracee continues without the user noticing
+        operation_message(state, Operation::Continue, task);
+        return;
+    }
+
+    refresh(state, regs.rip, task);
+    *task = batch_tasks(preload, task.take());
+}
+
+fn current_step_frame(pid: Pid) -> Result<StepFrame, ()> { // the function call next and finish work in
+    let frame = step_frame(get_registers(pid)?);
+    if frame.is_err() {
+        Dialog::error("Could not find the function the program is stopped in.", Some("Step Error"));
+    }
+    frame
+}
+
+fn scope_step(state: &mut window::State, pid: Pid, scope: StepScope, task: &mut Option<iced::Task<window::Message>>) { // like the source step, but only the lines of the function and the return address get a breakpoint
+    forget_history(); // the call runs without recording
+    if step(pid, None).is_err() {return;}; // we step away from the last line
+    let _ = wait(pid);
+
+    let frame = match &scope {
+        StepScope::Over(frame) | StepScope::Out(frame) => frame
+    };
+    let mut addresses = match scope {
+        StepScope::Over(_) => LINES.access().as_ref().unwrap().keys().filter(|address| frame.range.contains(address)).copied().collect(),
+        StepScope::Out(_) => Vec::new()
+    };
+    addresses.push(normal(frame.return_address));
+    addresses.sort();
+    addresses.dedup(); // a second breakpoint on the same address would save the 0xcc byte
+
+    let mut breakpoints = Breakpoints::new();
+    for address in addresses {
+        if let Ok(byte) = insert_breakpoint(pid, anti_normal(address)) {
+            breakpoints.add(address, byte);
+        }
+    }
+    if restart_tracee(pid, None).is_err() {
+        breakpoints.disable_all().unwrap();
+        return;
+    };
+    set_running(pid, false);
+    state.internal.source_step = Some(breakpoints);
+    state.internal.step_scope = Some(scope);
+    state.internal.stopped = false;
+    state_cont(state);
+    *task = Some(task_wait())
+}
+
+fn scope_reached(scope: &StepScope, regs: &user_regs_struct) -> bool { // whether the stop belongs to our function call (or its caller)
+    let (frame, over) = match scope {
+        StepScope::Over(frame) => (frame, true),
+        StepScope::Out(frame) => (frame, false)
+    };
+    if regs.rip == frame.return_address && regs.rsp >= frame.cfa { // the ret pops the return address, so the rsp of the caller is our cfa
+        return true;
+    }
+    over && frame.range.contains(&normal(regs.rip)) && step_frame(*regs).map_or(true, |current| current.cfa >= frame.cfa) // deeper recursive calls have a lower cfa
+}
+
+fn breakpoint_stop(state: &mut window::State, tid: Pid, address: u64) -> bool { // counts the hit and decides whether the user sees the stop, temporary breakpoints are removed when they stop
+    let condition = BREAKPOINTS.access().as_ref().and_then(|breakpoints| breakpoints.get(&address).and_then(|breakpoint| breakpoint.condition.clone()));
+    if let Some(condition) = condition && !condition_met(&condition, address) {
+        return false;
+    }
+
+    let mut bind = BREAKPOINTS.access();
+    let breakpoints = bind.as_mut().unwrap();
+    let breakpoint = match breakpoints.get_mut(&address) {
+        Some(breakpoint) => breakpoint,
+        None => return true // not ours (int3 compiled into the program)
+    };
+    breakpoint.hits += 1;
+    if breakpoint.hits <= breakpoint.ignore {
+        return false;
+    }
+    let log = breakpoint.log.clone();
+    if breakpoint.temporary {
+        breakpoints.remove(&address); // already out of the code, we are stopped
+    }
+    drop(bind);
+
+    match log {
+        Some(log) => { // logpoints never stop
+            let message = match eval::parse_message(&log) {
+                Ok(parts) => eval::message(&parts),
+                Err(err) => format!("<{}>", err)
+            };
+            let time = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            state.internal.pane.logs.push(LogEntry {time, tid, message});
+            false
+        },
+        None => true
+    }
+}
+
+fn condition_met(condition: &str, address: u64) -> bool { // conditions we cant evaluate stop the tracee, they are removed so the warning shows only once
+    match eval::parse(condition).and_then(|expression| eval::condition(&expression)) {
+        Ok(met) => met,
+        Err(err) => {
+            if let Some(breakpoint) = BREAKPOINTS.access().as_mut().and_then(|breakpoints| breakpoints.get_mut(&address)) {
+                breakpoint.condition = None;
+            }
+            Dialog::warning(&format!("Could not evaluate the breakpoint condition \"{}\":\n{}\n\nThe condition was removed, the breakpoint stops on every hit.", condition, err), Some("Condition Error"));
+            true
+        }
+    }
+}
+
+fn add_watchpoint(mut watchpoint: Watchpoint) -> Result<(), &'static str> {
+    if ![1, 2, 4, 8].contains(&watchpoint.size) {
+        return Err("Watchpoints can watch 1, 2, 4 or 8 bytes.");
+    }
+    if !watchpoint.address.is_multiple_of(watchpoint.size as u64) {
+        return Err("The address of the watchpoint has to be aligned to its size.");
+    }
+    watchpoint.value = read_memory(watchpoint.address, watchpoint.size as usize).map_err(|_| "The address of the watchpoint is not readable.")?;
+
+    let mut bind = WATCHPOINTS.access();
+    let watchpoints = bind.get_or_insert_default();
+    let index = watchpoints.iter().position(|slot| slot.is_none()).ok_or("All four debug registers are used by other watchpoints.")?;
+    watchpoints[index] = Some(watchpoint);
+    drop(bind);
+
+    for tid in watch_threads() {
+        if apply_watchpoints(tid).is_err() { // the kernel refused it, we leave the registers as they were
+            WATCHPOINTS.access().as_mut().unwrap()[index] = None;
+            for tid in watch_threads() {
+                let _ = apply_watchpoints(tid);
+            }
+            return Err("Could not set the debug registers of the tracee.");
+        }
+    }
+    Ok(())
+}
+
+fn watchpoint_hit(tid: Pid) -> Option<String> { // decoding DR6, returns the message for the status bar, with the old and the new value
+    let status = get_debug_register(tid, DR6).ok()?;
+    let _ = set_debug_register(tid, DR6, 0); // the status bits stay set until cleared
+
+    let mut bind = WATCHPOINTS.access();
+    let (index, watchpoint) = bind.as_mut()?.iter_mut().enumerate().find_map(|(index, slot)| match slot {
+        Some(watchpoint) if status & (1 << index) != 0 => Some((index, watchpoint)),
+        _ => None
+    })?;
+    if watchpoint.kind == WatchKind::Execute {
+        return Some(format!("Watchpoint {}: {} executed", index, watchpoint.label()));
+    }
+
+    let value = read_memory(watchpoint.address, watchpoint.size as usize).ok()?;
+    let number = |bytes: &Vec<u8>| bytes.iter().rev().fold(0u64, |number, byte| number << 8 | *byte as u64); // little endian
+    let message = if value == watchpoint.value {
+        format!("Watchpoint {}: {} = {} (read)", index, watchpoint.label(), number(&value))
+    } else {
+        format!("Watchpoint {}: {} = {} -> {}", index, watchpoint.label(), number(&watchpoint.value), number(&value))
+    };
+    watchpoint.value = value;
+    Some(message)
+}
+
+fn debug_control(watchpoints: &Watchpoints) -> u64 { // DR7, the local enable bit, type and length of every used debug register
+    let mut control = 0;
+    for (index, watchpoint) in watchpoints.iter().enumerate() {
+        if let Some(watchpoint) = watchpoint {
+            let kind = match watchpoint.kind {
+                WatchKind::Execute => 0b00,
+                WatchKind::Write => 0b01,
+                WatchKind::ReadWrite => 0b11
+            };
+            let length = match watchpoint.size {
+                1 => 0b00,
+                2 => 0b01,
+                8 => 0b10,
+                _ => 0b11
+            };
+            control |= 1 << (index * 2);
+            control |= (kind | length << 2) << (16 + index * 4);
+        }
+    }
+    control
+}
+
+fn apply_watchpoints(tid: Pid) -> Result<(), ()> { // debug registers are per thread, DR7 goes first so the kernel accepts the new addresses
+    let watchpoints = WATCHPOINTS.access().clone().unwrap_or_default();
+    set_debug_register(tid, DR7, 0)?;
+    for (index, watchpoint) in watchpoints.iter().enumerate() {
+        set_debug_register(tid, index, watchpoint.as_ref().map_or(0, |watchpoint| watchpoint.address))?;
+    }
+    set_debug_register(tid, DR7, debug_control(&watchpoints))
+}
+
+fn watch_threads() -> Vec<Pid> { // the threads of the focused process, the watched addresses belong to its memory
+    let pid = PID.access().unwrap();
+    match THREADS.access().as_ref() {
+        Some(threads) => threads.iter().filter(|(_, thread)| thread.process == pid).map(|(tid, _)| *tid).collect(),
+        None => Vec::new()
+    }
+}
+
+fn recording() -> bool {
+    RECORD.access().as_ref().is_some_and(|record| record.on) && CORE.access().is_none()
+}
+
+fn record_step(tid: Pid) -> Result<(), ()> { // saving the state before the next instruction, the recording supports only a single thread
+    if THREADS.access().as_ref().is_some_and(|threads| threads.len() > 1) {
+        RECORD.access().get_or_insert_default().on = false;
+        forget_history();
+        Dialog::error("Recording works only with single threaded programs, it was turned off.", Some("Record Error"));
+        return Err(());
+    }
+    let regs = get_registers(tid)?;
+    let fpregs = ptrace::getregset::<ptrace::regset::NT_PRFPREG>(tid).ok();
+    let snapshot = record::snapshot(regs, fpregs);
+    RECORD.access().get_or_insert_default().push(snapshot);
+    Ok(())
+}
+
+fn forget_history() { // the tracee ran (or was changed) without recording, the saved states dont lead to the current one
+    if let Some(record) = RECORD.access().as_mut() {
+        record.history.clear();
+    }
+}
+
+fn reverse(state: &mut window::State, until: impl Fn(u64) -> bool, task: &mut Option<iced::Task<window::Message>>) { // restoring the recorded states, the newest first, until one of them stops the reverse execution
+    if !state.internal.stopped || CORE.access().is_some() {
+        return;
+    }
+    let tid = current_thread();
+    let mut restored = None;
+    let mut steps = 0;
+    while let Some(snapshot) = RECORD.access().as_mut().and_then(|record| record.history.pop_back()) {
+        if record::restore_memory(&snapshot).is_err() {
+            Dialog::error("Could not restore the memory of the recorded step.", Some("Record Error"));
+            forget_history();
+            break;
+        }
+        steps += 1;
+        let stop = until(snapshot.regs.rip);
+        restored = Some(snapshot);
+        if stop {
+            break;
+        }
+    }
+    let snapshot = match restored {
+        Some(snapshot) => snapshot,
+        None => {Dialog::warning("There are no recorded steps to go back to.", Some("Record")); return;}
+    };
+
+    let regs = snapshot.regs;
+    if set_registers(tid, regs).is_err() {
+        return;
+    }
+    if let Some(fpregs) = snapshot.fpregs {
+        let _ = ptrace::setregset::<ptrace::regset::NT_PRFPREG>(tid, fpregs);
+    }
+    REGISTERS.sets(regs);
+    *FPREGS.access() = get_fp_registers(tid).ok();
+    if let Some(thread) = THREADS.access().as_mut().unwrap().get_mut(&tid) {
+        thread.regs = Some(regs);
+        thread.function = function_name(regs.rip);
+    }
+    let left = RECORD.access().as_ref().map_or(0, |record| record.history.len());
+    state.internal.reversed = Some(format!("Reversed {} step{} ({} left)", steps, if steps == 1 {""} else {"s"}, left));
+    state.internal.breakpoint = false;
+    state.internal.watchpoint = None;
+    state.internal.returned = None;
+    state.internal.catchpoint = None;
+    state.last_signal = None; // the signal came after this point
+    refresh(state, regs.rip, task);
+}
+
+fn batch_tasks(first: Option<iced::Task<window::Message>>, second: Option<iced::Task<window::Message>>) -> Option<iced::Task<window::Message>> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(iced::Task::batch([first, second])),
+        (first, second) => first.or(second)
+    }
+}
+
+fn watch_value(expression: &str) -> String {
+    match eval::watch(expression) {
+        Ok(value) => value,
+        Err(err) => format!("<{}>", err)
+    }
+}
+
+fn refresh(state: &mut window::State, rip: u64, task: &mut Option<iced::Task<window::Message>>) { // updating the panes for the current thread and address
     let mut tasks = Vec::new();
 
     if ui::check_for_assembly(state) { // performance reasons
-        tasks.push(task_assembly(regs.rip)); // updating assembly
+        tasks.push(task_assembly(rip)); // updating assembly
     };
 
     if state.internal.no_debug { // if no debug, end here
@@ -535,8 +1603,12 @@ fn handle(state: &mut window::State, status: wait::WaitStatus, task: &mut Option
         return;
     }
 
+    for (expression, value) in state.internal.pane.watches.iter_mut() { // the watches are evaluated again on every stop
+        *value = watch_value(expression);
+    }
+
     let bind = LINES.access();
-    let file = bind.as_ref().unwrap().get_line(regs.rip);
+    let file = bind.as_ref().unwrap().get_line(rip);
     state.internal.pane.file = file.map(|index| index.clone());
     drop(bind);
 
@@ -566,17 +1638,663 @@ fn handle(state: &mut window::State, status: wait::WaitStatus, task: &mut Option
     *task = Some(iced::Task::batch(tasks)); // return all of the tasks
 }
 
+pub fn current_thread() -> Pid { // the selected thread, the main thread by default
+    let thread = *THREAD.access();
+    thread.unwrap_or_else(|| PID.access().unwrap())
+}
+
+fn set_running(tid: Pid, stepping: bool) {
+    if let Some(thread) = THREADS.access().as_mut().unwrap().get_mut(&tid) {
+        thread.running = true;
+        thread.stepping = stepping;
+        thread.rewound = false;
+    }
+}
+
+fn attach_threads(pid: Pid) { // when attaching, the other threads of the process have to be attached one by one
+    let tasks = match std::fs::read_dir(format!("/proc/{pid}/task")) {
+        Ok(tasks) => tasks,
+        Err(_) => return
+    };
+    for entry in tasks.flatten() {
+        let tid = match entry.file_name().to_str().and_then(|name| name.parse::<i32>().ok()) {
+            Some(tid) => Pid::from_raw(tid),
+            None => continue
+        };
+        if tid == pid || ptrace::attach(tid).is_err() { // the thread might have exited since
+            continue;
+        }
+        if !matches!(wait(tid), Ok(wait::WaitStatus::Stopped(..))) {
+            continue;
+        }
+        let _ = set_options(tid);
+        let mut thread = Thread::new(pid);
+        thread.update(tid);
+        THREADS.access().as_mut().unwrap().insert(tid, thread);
+    }
+}
+
+fn thread_clone(tid: Pid) { // the thread created a new one, we trace it the same way as its parent
+    let mut bind = THREADS.access();
+    let threads = bind.as_mut().unwrap();
+    let (process, stepping) = match threads.get(&tid) {
+        Some(thread) => (thread.process, thread.stepping),
+        None => (PID.access().unwrap(), false)
+    };
+
+    if let Ok(new) = ptrace::getevent(tid) {
+        let new = Pid::from_raw(new as i32);
+        if !threads.contains_key(&new) { // the new thread starts with a SIGSTOP, unless we have already seen it
+            let _ = wait(new);
+        }
+        let mut thread = Thread::new(process);
+        thread.running = !stepping; // while stepping, the new thread waits for the next continue
+        threads.insert(new, thread);
+        if process == PID.access().unwrap() && WATCHPOINTS.access().is_some() { // debug registers are not inherited
+            let _ = apply_watchpoints(new);
+        }
+        if !stepping {
+            let _ = resume(new, None);
+        }
+    }
+    drop(bind);
+
+    resume_thread(tid);
+}
+
+fn thread_fork(state: &mut window::State, tid: Pid, vfork: bool, resume: bool) { // the tracee forked, the child is traced automatically, the setting decides what happens to it
+    let child = match ptrace::getevent(tid) {
+        Ok(child) => Pid::from_raw(child as i32),
+        Err(_) => {
+            if resume {resume_thread(tid);}
+            return;
+        }
+    };
+    if !THREADS.access().as_ref().unwrap().contains_key(&child) { // the child starts with a SIGSTOP, unless we have already seen it
+        let _ = wait(child);
+    }
+    THREADS.access().as_mut().unwrap().remove(&child);
+    let parent = thread_process(tid);
+    let stepping = THREADS.access().as_ref().unwrap().get(&tid).is_some_and(|thread| thread.stepping);
+
+    let follow = LAUNCH.access().as_ref().unwrap().follow_fork.unwrap_or(config::FollowFork::parent);
+    match follow {
+        config::FollowFork::parent => {
+            if !vfork { // the child got a copy of our breakpoints, a vfork child shares the memory with the parent though
+                strip_breakpoints(state, child);
+            }
+            let _ = ptrace::detach(child, None);
+            if resume {
+                resume_thread(tid);
+            }
+        },
+        config::FollowFork::child => { // we let go of the parent (and its threads) and continue with the child only
+            strip_breakpoints(state, parent); // with vfork this removes them from the child as well, until it executes its program
+            let threads: Vec<Pid> = THREADS.access().as_ref().unwrap().iter().filter(|(_, thread)| thread.process == parent).map(|(thread_id, _)| *thread_id).collect();
+            for thread_id in threads {
+                let thread = THREADS.access().as_mut().unwrap().remove(&thread_id).unwrap();
+                if thread_id != tid && thread.running {
+                    continue; // running threads cannot be detached, they lose the tracer when we leave the process
+                }
+                if thread.stop_pending && ptrace::cont(thread_id, None).is_ok() {
+                    let _ = wait(thread_id);
+                }
+                let _ = ptrace::detach(thread_id, thread.signal);
+            }
+            if let Some(inferiors) = INFERIORS.access().as_mut() {
+                inferiors.remove(&parent);
+            }
+            add_inferior(child);
+            if parent == PID.access().unwrap() {
+                focus(state, child);
+            }
+            if resume { // the child continues the same way the parent did
+                set_running(child, stepping);
+                resume_thread(child);
+            }
+        },
+        config::FollowFork::both => {
+            add_inferior(child);
+            if resume {
+                resume_thread(tid);
+            }
+            if resume && !stepping { // while stepping, the child waits for the next continue
+                set_running(child, false);
+                resume_thread(child);
+            }
+        }
+    }
+}
+
+fn thread_exec(state: &mut window::State, pid: Pid, task: &mut Option<iced::Task<window::Message>>) -> Result<(), ()> { // the process executed a new program, we load its file and debug data
+    let path = get_tracee_path(&PathBuf::from(format!("/proc/{pid}/")))?;
+    if let Some(threads) = THREADS.access().as_mut() { // the other threads of the process are gone, the one that executed now has the pid of the process
+        threads.retain(|thread_id, thread| thread.process != pid || *thread_id == pid);
+        threads.entry(pid).or_insert_with(|| Thread::new(pid));
+    }
+
+    WATCHPOINTS.none(); // the kernel clears the debug registers on exec
+
+    let file = FILE.access().clone();
+    let old: Vec<Pid> = INFERIORS.access().as_ref().map(|inferiors| {
+        inferiors.iter().filter(|(other, path)| **other != pid && Some(*path) == file.as_ref()).map(|(other, _)| *other).collect()
+    }).unwrap_or_default();
+    for other in old { // the processes still running the old program cant keep its breakpoints
+        strip_breakpoints(state, other);
+    }
+    state.internal.source_step = None;
+    state.internal.step_scope = None;
+    INFERIORS.access().get_or_insert_default().insert(pid, path.clone());
+
+    clear_file(state);
+    unsafe {
+        DATA = object::read_file(&path)
+    }
+    FILE.sets(path.clone());
+    state.internal.static_exec = false;
+    let session = session::load(&path).unwrap_or_default(); // the breakpoints and watches of the new program, the layout and launch settings stay
+    state.internal.pane.watches = session.watches.iter().flatten().map(|expression| (expression.clone(), String::new())).collect();
+    state.internal.pending_breakpoints = session.breakpoints.unwrap_or_default();
+    state.internal.no_debug = dwarf_set(state).is_err();
+    if state.internal.no_debug {
+        BREAKPOINTS.sets(Breakpoints::new());
+    } else {
+        panes_preload(state, task); // code panes with the main file of the new program
+        session::restore_breakpoints(state);
+    }
+    focus(state, pid);
+    linker_setup(&process_auxv(pid)); // the new program has its own dynamic linker
+    Ok(())
+}
+
+fn add_inferior(pid: Pid) { // a new traced process (from a fork) running the same program as its parent, stopped
+    let mut thread = Thread::new(pid);
+    thread.update(pid);
+    THREADS.access().as_mut().unwrap().insert(pid, thread);
+    let path = FILE.access().clone().unwrap();
+    INFERIORS.access().get_or_insert_default().insert(pid, path);
+}
+
+fn focus(state: &mut window::State, pid: Pid) { // switching the inferior, whose memory and maps are used in the panes
+    if PID.access().is_some_and(|current| current == pid) {
+        return;
+    }
+    PID.sets(pid);
+    let _ = process_setup(state, pid);
+}
+
+fn process_setup(state: &window::State, pid: Pid) -> Result<(), ()> { // loading the memory access and maps of a traced process
+    let proc_path = PathBuf::from(format!("/proc/{pid}/"));
+    let path = get_tracee_path(&proc_path)?;
+    let maps = get_process_maps(&proc_path)?;
+
+    exec_shift(state, &maps, path.to_str().unwrap());
+    MAPS.sets(maps);
+    MEMORY.sets(open_memory(&proc_path)?);
+    PROC_PATH.sets(proc_path);
+    INFERIORS.access().get_or_insert_default().insert(pid, path);
+    Ok(())
+}
+
+fn exec_shift(state: &window::State, maps: &[MemoryMap], path: &str) {
+    EXEC_SHIFT.none();
+    for map in maps { // we find our exec shift, unless its a static executable
+        if map.name != path {
+            continue;
+        }
+        if map.offset == 0 {
+            if state.internal.static_exec {break;}
+            EXEC_SHIFT.sets(map.range.start);
+            break;
+        }
+    };
+}
+
+fn core_setup(state: &mut window::State, core: Core, task: &mut Option<iced::Task<window::Message>>) { // the panes show the crashed process, like a stopped tracee
+    let maps = core.maps();
+    if let Some(path) = core.executable() {
+        exec_shift(state, &maps, path);
+    }
+    MAPS.sets(maps);
+    let pid = core.pid;
+    let auxv = core.auxv.clone();
+    let threads = core.threads.clone();
+    let fpregs = core.fpregs.clone();
+    state.status = Some(wait::WaitStatus::Signaled(pid, core.signal.unwrap_or(Signal::SIGKILL), true));
+    CORE.sets(core);
+
+    linker_setup(&auxv); // the libraries are read from the link_map in the core memory
+    sync_libraries(pid);
+
+    THREADS.sets(threads.iter().map(|(tid, regs)| {
+        let mut thread = Thread::new(pid);
+        thread.regs = Some(*regs);
+        thread.function = function_name(regs.rip);
+        (*tid, thread)
+    }).collect());
+    let (tid, regs) = match threads.first() {
+        Some(thread) => *thread,
+        None => return
+    };
+    THREAD.sets(tid);
+    REGISTERS.sets(regs);
+    *FPREGS.access() = fpregs.get(&tid).cloned();
+    state.internal.stopped_thread = Some(tid);
+
+    for pane in state.layout.panes.iter_mut().map(|(_, pane)| pane) { // the memory panes start at the stack of the crash
+        if let ui::Pane::Memory(inner) = pane {
+            inner.address = regs.rsp;
+            inner.field = ui::Base::form(&ui::Base::Hex, regs.rsp);
+            ui::update_memory(inner);
+        }
+    }
+    refresh(state, regs.rip, task);
+}
+
+fn linker_setup(auxv: &[u8]) { // finding the r_debug and _dl_debug_state of the dynamic linker, static executables dont have one
+    LINKER.none();
+    let base = match auxv_entry(auxv, nix::libc::AT_BASE) { // where the linker is loaded
+        Some(base) if base != 0 => base,
+        _ => return
+    };
+    let path = match MAPS.access().as_ref().and_then(|maps| maps.iter().find(|map| map.range.start == base).map(|map| map.name.clone())) {
+        Some(path) => path,
+        None => return
+    };
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(_) => return
+    };
+    let object = match object_foreign::File::parse(&*data) {
+        Ok(object) => object,
+        Err(_) => return
+    };
+    let symbol = |name: &str| object.dynamic_symbols().find(|symbol| symbol.name() == Ok(name)).map(|symbol| base + symbol.address());
+    if let (Some(r_debug), Some(address)) = (symbol("_r_debug"), symbol("_dl_debug_state")) {
+        LINKER.sets(Linker {r_debug, address, byte: None});
+    }
+}
+
+fn auxv_entry(auxv: &[u8], key: u64) -> Option<u64> { // the auxiliary vector is a list of key and value pairs
+    auxv.chunks_exact(16).find(|entry| u64::from_le_bytes(entry[..8].try_into().unwrap()) == key).map(|entry| u64::from_le_bytes(entry[8..].try_into().unwrap()))
+}
+
+fn process_auxv(pid: Pid) -> Vec<u8> {
+    std::fs::read(format!("/proc/{pid}/auxv")).unwrap_or_default()
+}
+
+fn sync_libraries(pid: Pid) { // walking the link_map list of the dynamic linker, the new libraries get loaded and the missing ones become pending
+    let r_debug = match LINKER.access().as_ref() {
+        Some(linker) => linker.r_debug,
+        None => return
+    };
+    let read = |address: u64| read_word(pid, address);
+    if read(r_debug + 24).map_or(true, |state| state as u32 != 0) { // r_state, the list is complete only while RT_CONSISTENT
+        return;
+    }
+
+    let mut loaded = Vec::new();
+    let mut map = read(r_debug + 8).unwrap_or(0); // r_map
+    for _ in 0..LINK_LIMIT {
+        if map == 0 {
+            break;
+        }
+        let (Ok(bias), Ok(name), Ok(next)) = (read(map), read(map + 8), read(map + 24)) else { // l_addr, l_name and l_next
+            break;
+        };
+        let name = read_string(pid, name);
+        if name.starts_with('/') { // the executable has an empty name and the vdso has no file
+            loaded.push((PathBuf::from(name), bias));
+        }
+        map = next;
+    }
+
+    let mut bind = LIBRARIES.access();
+    let libraries = bind.get_or_insert_default();
+    libraries.iter_mut().for_each(|library| library.bias = None); // unloaded by dlclose
+    let mut new = Vec::new();
+    for (path, bias) in loaded {
+        match libraries.iter_mut().find(|library| library.path == path) {
+            Some(library) => library.bias = Some(bias),
+            None => new.push((path, bias))
+        }
+    }
+    let mut image = libraries.len();
+    drop(bind);
+    for (path, bias) in new { // the SOURCE, LINES and FUNCTIONS get locked while loading
+        image += 1;
+        let library = Library::load(path, bias, image);
+        LIBRARIES.access().get_or_insert_default().push(library);
+    }
+}
+
+pub fn read_word(pid: Pid, address: u64) -> Result<u64, ()> { // from the tracee or the core, without the error dialogs of read_memory
+    if let Some(core) = CORE.access().as_ref() {
+        return core.read(address, 8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+    }
+    ptrace::read(pid, address as ptrace::AddressType).map(|long| long as u64).map_err(|_| ())
+}
+
+fn read_string(pid: Pid, mut address: u64) -> String { // null terminated string from the tracee, word by word (no errors for the unmapped memory after it)
+    let mut bytes = Vec::new();
+    while bytes.len() < 4096 {
+        let word = match read_word(pid, address) {
+            Ok(word) => word.to_le_bytes(),
+            Err(_) => break
+        };
+        match word.iter().position(|byte| *byte == 0) {
+            Some(end) => {
+                bytes.extend_from_slice(&word[..end]);
+                break;
+            },
+            None => bytes.extend_from_slice(&word)
+        }
+        address += 8;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn linker_insert() { // the hidden breakpoint of the linker hook, only while continuing like the other breakpoints
+    let mut bind = LINKER.access();
+    let linker = match bind.as_mut() {
+        Some(linker) if linker.byte.is_none() => linker,
+        _ => return
+    };
+    for pid in image_inferiors() { // the forks load libraries too
+        let _ = insert_breakpoint(pid, linker.address);
+    }
+    linker.byte = insert_breakpoint(PID.access().unwrap(), linker.address).ok();
+}
+
+fn linker_remove() {
+    let mut bind = LINKER.access();
+    let (address, byte) = match bind.as_mut() {
+        Some(linker) => match linker.byte.take() {
+            Some(byte) => (linker.address, byte),
+            None => return
+        },
+        None => return
+    };
+    for pid in image_inferiors() {
+        let _ = remove_breakpoint(pid, address, byte);
+    }
+    let _ = remove_breakpoint(PID.access().unwrap(), address, byte);
+}
+
+fn strip_breakpoints(state: &window::State, pid: Pid) { // removing the inserted breakpoints out of a process we stop tracing (or that runs a different program now)
+    match &state.internal.source_step {
+        Some(breakpoints) => for (address, breakpoint) in breakpoints.iter() {
+            let _ = remove_breakpoint(pid, anti_normal(*address), breakpoint.byte);
+        },
+        None => if !state.internal.stopped { // breakpoints are in the memory only while continuing
+            for (address, breakpoint) in BREAKPOINTS.access().as_ref().unwrap().iter().filter(|(_, breakpoint)| breakpoint.inserted) {
+                let _ = remove_breakpoint(pid, anti_normal(*address), breakpoint.byte);
+            }
+            if let Some(Linker {address, byte: Some(byte), ..}) = LINKER.access().as_ref() {
+                let _ = remove_breakpoint(pid, *address, *byte);
+            }
+        }
+    }
+}
+
+fn image_inferiors() -> Vec<Pid> { // the other traced processes running the loaded executable (forks)
+    let pid = PID.access().unwrap();
+    let file = FILE.access().clone();
+    match INFERIORS.access().as_ref() {
+        Some(inferiors) => inferiors.iter().filter(|(other, path)| **other != pid && Some(*path) == file.as_ref()).map(|(other, _)| *other).collect(),
+        None => Vec::new()
+    }
+}
+
+fn thread_process(tid: Pid) -> Pid { // the process of the thread
+    let process = THREADS.access().as_ref().and_then(|threads| threads.get(&tid).map(|thread| thread.process));
+    process.unwrap_or_else(|| PID.access().unwrap())
+}
+
+fn resume_thread(tid: Pid) { // restarts the thread the same way it was restarted before (step or continue)
+    let stepping = THREADS.access().as_ref().unwrap().get(&tid).is_some_and(|thread| thread.stepping);
+    let _ = if stepping {ptrace::step(tid, None)} else {resume(tid, None)};
+}
+
+fn pass_signal(tid: Pid, signal: Option<Signal>) { // restarts the thread that got a signal which doesnt stop the tracee
+    let stepping = THREADS.access().as_ref().unwrap().get(&tid).is_some_and(|thread| thread.stepping);
+    let _ = if stepping {ptrace::step(tid, signal)} else {resume(tid, signal)};
+}
+
+fn log_signal(state: &mut window::State, tid: Pid, signal: Signal) {
+    let log = &mut state.internal.pane.signals;
+    log.push(format!("[{}] {}", tid, signal));
+    if log.len() > SIGNAL_LOG_LIMIT {
+        log.remove(0);
+    }
+}
+
+fn resume(tid: Pid, signal: Option<Signal>) -> Result<(), nix::errno::Errno> { // PTRACE_CONT, or PTRACE_SYSCALL while the syscalls are logged or caught
+    match SYSCALLS.access().as_ref().is_some_and(|tracing| tracing.active()) {
+        true => ptrace::syscall(tid, signal),
+        false => ptrace::cont(tid, signal)
+    }
+}
+
+fn syscall_stop(state: &mut window::State, tid: Pid) -> Option<String> { // logging the syscall of the thread, returns the message of the catchpoint it hit
+    let info = get_syscall_info(tid).ok()?;
+    let tracing = SYSCALLS.access().clone().unwrap_or_default();
+    let log = &mut state.internal.pane.syscalls;
+    match info.op {
+        nix::libc::PTRACE_SYSCALL_INFO_ENTRY => {
+            let entry = unsafe {info.u.entry};
+            for pending in log.iter_mut().filter(|pending| pending.tid == tid && pending.result.is_none()) { // the exit isnt reported when the syscall was stepped over
+                pending.result = Some(String::from("?"));
+            }
+            let call = syscall::call(tid, entry.nr, entry.args);
+            let catchpoint = tracing.catchpoints.iter().find(|catchpoint| catchpoint.matches(entry.nr, &entry.args)).map(|catchpoint| format!("Catchpoint {}: {}", catchpoint, call));
+            if tracing.log {
+                log.push(syscall::Entry {tid, nr: entry.nr, call, result: None});
+                if log.len() > SYSCALL_LOG_LIMIT {
+                    log.drain(..log.len() - SYSCALL_LOG_LIMIT);
+                }
+            }
+            catchpoint
+        },
+        nix::libc::PTRACE_SYSCALL_INFO_EXIT => {
+            let exit = unsafe {info.u.exit};
+            if let Some(pending) = log.iter_mut().rev().find(|pending| pending.tid == tid) && pending.result.is_none() {
+                pending.result = Some(syscall::result(pending.nr, exit.sval, exit.is_error != 0));
+            }
+            None
+        },
+        _ => None
+    }
+}
+
+fn release_stop(tid: Pid, status: wait::WaitStatus) { // the stop we detach on can be any stop, the breakpoint hit is undone and only the signals of the program are delivered
+    let info = get_sig_info(tid).ok();
+    let ours = matches!(status, wait::WaitStatus::Stopped(_, Signal::SIGSTOP)) && info.is_some_and(|info| info.si_code == SI_TKILL);
+    if ours { // our SIGSTOP of the leader, otherwise it stays pending and detach_tracee consumes it
+        if let Some(thread) = THREADS.access().as_mut().and_then(|threads| threads.get_mut(&tid)) {
+            thread.stop_pending = false;
+        }
+    }
+    let trap = info.is_some_and(|info| info.si_signo == Signal::SIGTRAP as i32 && matches!(info.si_code, TRAP_BRKPT|SI_KERNEL));
+    if trap && let Ok(mut regs) = get_registers(tid) && BREAKPOINTS.access().as_ref().is_some_and(|breakpoints| breakpoints.contains_key(&normal(regs.rip - 1))) {
+        regs.rip -= 1; // the original byte gets restored, so the instruction runs again
+        let _ = set_registers(tid, regs);
+    }
+    let signal = match status {
+        wait::WaitStatus::Stopped(_, signal) if signal != Signal::SIGTRAP && !ours => Some(signal),
+        _ => None
+    };
+    if let Some(thread) = THREADS.access().as_mut().and_then(|threads| threads.get_mut(&tid)) {
+        thread.signal = signal;
+    }
+}
+
+fn swallow_stop(tid: Pid) -> bool { // SIGSTOPs we sent to the threads are not reported to the user, returns true if the stop was ours
+    let mut bind = THREADS.access();
+    let threads = bind.as_mut().unwrap();
+    match threads.get_mut(&tid) {
+        Some(thread) if thread.stop_pending => thread.stop_pending = false,
+        Some(_) => return false,
+        None => { // a new thread (or child) stopped before its parent reported the event, it gets resumed with the event
+            threads.insert(tid, Thread::new(tid));
+            return true;
+        }
+    };
+    drop(bind);
+
+    resume_thread(tid);
+    true
+}
+
+fn stop_threads(state: &mut window::State, tid: Pid) { // all-stop, when one thread stops we stop all of the others (and save their registers)
+    let mut bind = THREADS.access();
+    let threads = bind.as_mut().unwrap();
+    let mut new_threads = Vec::new();
+    let mut forks = Vec::new();
+    let mut exited = Vec::new();
+    let mut syscalls = Vec::new();
+    let mut printed = Vec::new();
+
+    for (thread_id, thread) in threads.iter_mut() {
+        let running = thread.running && *thread_id != tid;
+        thread.running = false;
+        thread.stepping = false;
+        if !running {
+            continue;
+        }
+        if stop_thread(thread.process, *thread_id).is_err() {
+            exited.push(*thread_id);
+            continue;
+        }
+        match wait(*thread_id) {
+            Ok(wait::WaitStatus::Stopped(_, Signal::SIGSTOP)) => (),
+            Ok(wait::WaitStatus::Stopped(_, Signal::SIGTRAP)) => { // the thread hit a breakpoint at the same time, we move it back so it hits it again after continue
+                thread.stop_pending = true;
+                let breakpoint = matches!(ptrace::getsiginfo(*thread_id).map(|info| info.si_code), Ok(TRAP_BRKPT|SI_KERNEL));
+                if let (true, Ok(mut regs)) = (breakpoint, ptrace::getregs(*thread_id)) {
+                    regs.rip -= 1;
+                    thread.rewound = ptrace::setregs(*thread_id, regs).is_ok();
+                }
+            },
+            Ok(wait::WaitStatus::PtraceSyscall(_)) => { // the thread entered or left a syscall at the same time, our SIGSTOP comes after it
+                thread.stop_pending = true;
+                syscalls.push(*thread_id);
+            },
+            Ok(wait::WaitStatus::Stopped(_, signal)) => { // a signal for the thread, we deliver it later
+                thread.stop_pending = true;
+                match config::disposition(signal) {
+                    config::Disposition::ignore => (),
+                    config::Disposition::print => {
+                        thread.signal = Some(signal);
+                        printed.push((*thread_id, signal));
+                    },
+                    _ => thread.signal = Some(signal)
+                }
+            },
+            Ok(wait::WaitStatus::PtraceEvent(_, _, event)) => {
+                thread.stop_pending = true;
+                if event == ptrace::Event::PTRACE_EVENT_FORK as i32 || event == ptrace::Event::PTRACE_EVENT_VFORK as i32 {
+                    forks.push((*thread_id, event == ptrace::Event::PTRACE_EVENT_VFORK as i32));
+                }
+                if event != ptrace::Event::PTRACE_EVENT_CLONE as i32 {
+                    continue;
+                }
+                if let Ok(new) = ptrace::getevent(*thread_id) { // the new thread stays stopped as well
+                    let new = Pid::from_raw(new as i32);
+                    let _ = wait(new);
+                    new_threads.push((new, thread.process));
+                }
+            },
+            _ => exited.push(*thread_id)
+        }
+    }
+
+    for thread_id in exited {
+        threads.remove(&thread_id);
+    }
+    for (thread_id, process) in new_threads {
+        threads.insert(thread_id, Thread::new(process));
+    }
+    for (thread_id, thread) in threads.iter_mut() {
+        thread.update(*thread_id);
+    }
+    drop(bind);
+
+    for (thread_id, vfork) in forks { // the children of the forks stay stopped as well
+        thread_fork(state, thread_id, vfork, false);
+    }
+    for thread_id in syscalls { // only logged, the tracee is stopping already
+        syscall_stop(state, thread_id);
+    }
+    for (thread_id, signal) in printed {
+        log_signal(state, thread_id, signal);
+    }
+}
+
+fn step_threads() { // before continuing, every thread sitting on a breakpoint has to step away from it (unless it didnt hit it yet)
+    let threads: Vec<(Pid, Thread)> = THREADS.access().as_ref().unwrap().clone().into_iter().collect();
+    let bind = BREAKPOINTS.access();
+    let breakpoints = bind.as_ref().unwrap();
+    for (thread_id, thread) in threads {
+        if thread.rewound {
+            continue;
+        }
+        if thread.regs.is_some_and(|regs| breakpoints.contains_key(&normal(regs.rip))) && step(thread_id, None).is_ok() { // threads stopped elsewhere might be inside of a syscall, stepping them would block
+            let _ = wait(thread_id);
+        }
+    }
+}
+
+fn resume_threads(tid: Pid) { // continuing all of the threads, apart from the one already restarted
+    let mut bind = THREADS.access();
+    for (thread_id, thread) in bind.as_mut().unwrap().iter_mut() {
+        if *thread_id == tid {
+            continue;
+        }
+        if resume(*thread_id, thread.signal.take()).is_ok() {
+            thread.running = true;
+            thread.rewound = false;
+        }
+    }
+}
+
 fn reset() { // reset TRACE globals
     STDIO.none();
     PID.none();
+    INFERIORS.none();
+    THREADS.none();
+    THREAD.none();
+    WATCHPOINTS.none();
     PROC_PATH.none();
     EXEC_SHIFT.none();
     MEMORY.none();
     REGISTERS.none();
+    FPREGS.none();
+    forget_history();
+    for checkpoint in CHECKPOINTS.access().take().unwrap_or_default() { // the copies end with the tracee
+        let _ = ptrace::detach(checkpoint.pid, Signal::SIGKILL);
+    }
     MAPS.none();
+    LINKER.none();
+    CORE.none();
+    if let Some(libraries) = LIBRARIES.access().as_mut() { // the libraries stay indexed for the next run, their breakpoints are pending until then
+        libraries.iter_mut().for_each(|library| library.bias = None);
+    }
 }
 
 fn reset_file(state: &mut window::State) -> Result<(), ()> { // reset selected file and GLOBALS
+    if PID.access().is_some() && state.internal.attached {
+        if !state.internal.stopped {
+            Dialog::warning("Pause the attached process before discarding the file.", None);
+            return Err(());
+        }
+        match Dialog::warning_choice("The debugger is still attached to the process. Are you sure you want to detach and discard of the file?", None) {
+            rfd::MessageDialogResult::No => return Err(()),
+            _ => detach_tracee(PID.access().unwrap())?
+        }
+        state.internal.attached = false;
+        reset();
+    }
     if PID.access().is_some() {
         match Dialog::warning_choice("The program is still running. Are you sure you want to stop the process and discard of the file?", None) {
             rfd::MessageDialogResult::No => return Err(()),
@@ -587,21 +2305,31 @@ fn reset_file(state: &mut window::State) -> Result<(), ()> { // reset selected f
         }
         reset();
     }
+    if CORE.access().is_some() { // the core belongs to the discarded file
+        reset();
+    }
+    state.internal.stopped = false;
+    clear_file(state);
+    Ok(())
+}
+
+fn clear_file(state: &mut window::State) { // clearing the file GLOBALS (the tracee is left alone)
+    session::save(state); // the last state of the session, before it is gone
     state.internal.pane.comp_dir = None;
     state.internal.pane.file = None;
     state.internal.pane.stack = None;
-    state.internal.stopped = false;
     FILE.none();
     DWARF.none();
     EHFRAME.none();
     BREAKPOINTS.none();
     SOURCE.none();
     LINES.none();
+    PROLOGUES.none();
     FUNCTIONS.none();
+    LIBRARIES.none();
     unsafe {
         DATA = Vec::new()
     };
-    Ok(())
 }
 
 fn state_cont(state: &mut window::State) { // set state to continue
@@ -648,7 +2376,7 @@ fn assembly_update(state: &mut window::State, rip: u64) -> Option<iced::Task<win
 pub fn open_memory(proc_path: &PathBuf) -> Result<File, ()> { // opens the memory file from the proc_fs, (creating the access to the tracees memory)
     let mut path = proc_path.clone();
     path.push("mem");
-    match File::open(path) {
+    match File::options().read(true).write(true).open(path) { // writable for the Memory pane edits
         Ok(file) => Ok(file),
         Err(err) => {Dialog::error(&format!("Could not open memory of the tracee: {}", err), Some("Trace Error")); Err(())}
     }
@@ -767,6 +2495,215 @@ pub fn get_registers(pid: Pid) -> Result<user_regs_struct, ()> { // wrapper for
     }
 }
 
+fn get_xmm0(pid: Pid) -> [u8; 16] { // wrapper for PTRACE_GETREGSET (NT_PRFPREG), only the xmm0 where floats are returned
+    let mut xmm0 = [0; 16];
+    if let Ok(fpregs) = ptrace::getregset::<ptrace::regset::NT_PRFPREG>(pid) {
+        for (i, word) in fpregs.xmm_space[..4].iter().enumerate() {
+            xmm0[i*4..i*4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+    }
+    xmm0
+}
+
+fn get_fp_registers(tid: Pid) -> Result<FpRegisters, ()> { // wrapper for PTRACE_GETREGSET (NT_PRFPREG for the x87 and SSE, NT_X86_XSTATE for the AVX)
+    let fpregs = ptrace::getregset::<ptrace::regset::NT_PRFPREG>(tid).map_err(|_| ())?;
+    let bytes = |words: &[u32]| -> Vec<u8> {words.iter().flat_map(|word| word.to_le_bytes()).collect()};
+    let st_space = bytes(&fpregs.st_space); // 16 bytes for each of the registers
+    let xmm_space = bytes(&fpregs.xmm_space);
+
+    let mut xstate = vec![0u8; XSTATE_SIZE];
+    let mut vector = nix::libc::iovec {iov_base: xstate.as_mut_ptr() as *mut c_void, iov_len: xstate.len()};
+    let ymm = match unsafe {nix::libc::ptrace(nix::libc::PTRACE_GETREGSET, tid.as_raw(), NT_X86_XSTATE, &mut vector as *mut nix::libc::iovec)} {
+        -1 => None,
+        _ => xstate_ymm(&xstate[..vector.iov_len])
+    };
+
+    Ok(FpRegisters {
+        st: std::array::from_fn(|i| st_space[i*16..i*16 + 10].try_into().unwrap()),
+        mxcsr: fpregs.mxcsr,
+        xmm: std::array::from_fn(|i| xmm_space[i*16..i*16 + 16].try_into().unwrap()),
+        ymm
+    })
+}
+
+fn xstate_ymm(xstate: &[u8]) -> Option<[[u8; 16]; 16]> { // the upper halves of the YMM registers in the XSAVE area, None without AVX
+    if xstate.len() < XSTATE_YMM + 256 { // no AVX on this cpu
+        return None;
+    }
+    let avx = u64::from_le_bytes(xstate[XSTATE_BV..XSTATE_BV + 8].try_into().unwrap()) & 4 != 0; // the upper halves are zero in their initial state
+    Some(std::array::from_fn(|i| if avx {xstate[XSTATE_YMM + i*16..XSTATE_YMM + i*16 + 16].try_into().unwrap()} else {[0; 16]}))
+}
+
+fn fxsave_registers(area: &[u8]) -> Option<FpRegisters> { // the legacy FXSAVE area of a core (the user_fpregs_struct), the mxcsr at 24, the ST0-7 at 32 and the XMM0-15 at 160
+    if area.len() < FXSAVE_SIZE {
+        return None;
+    }
+    Some(FpRegisters {
+        st: std::array::from_fn(|i| area[32 + i*16..32 + i*16 + 10].try_into().unwrap()),
+        mxcsr: u32::from_le_bytes(area[24..28].try_into().unwrap()),
+        xmm: std::array::from_fn(|i| area[160 + i*16..160 + i*16 + 16].try_into().unwrap()),
+        ymm: None
+    })
+}
+
+fn fork_process(pid: Pid) -> Result<Pid, ()> { // injecting a fork syscall into the stopped process, the child stays stopped (traced thanks to the fork option)
+    let regs = get_registers(pid)?;
+    let memory = open_memory(&PathBuf::from(format!("/proc/{pid}/")))?;
+    let mut code = [0u8; SYSCALL.len()];
+    memory.read_exact_at(&mut code, regs.rip).map_err(|_| ())?;
+    memory.write_all_at(&SYSCALL, regs.rip).map_err(|_| ())?;
+
+    let mut call = regs;
+    call.rax = nix::libc::SYS_fork as u64;
+    call.orig_rax = u64::MAX; // no syscall restart of an interrupted call
+    let child = (|| {
+        set_registers(pid, call)?;
+        step(pid, None)?;
+        let child = match wait(pid) {
+            Ok(wait::WaitStatus::PtraceEvent(_, _, event)) if event == ptrace::Event::PTRACE_EVENT_FORK as i32 => Pid::from_raw(ptrace::getevent(pid).map_err(|_| ())? as i32),
+            _ => return Err(()) // a signal came first
+        };
+        step(pid, None)?; // the end of the syscall
+        let _ = wait(pid);
+        let _ = wait(child); // the SIGSTOP of the new child
+        Ok(child)
+    })();
+
+    let _ = memory.write_all_at(&code, regs.rip); // the parent continues as if nothing happened
+    let _ = set_registers(pid, regs);
+    let child = child?;
+    let child_memory = open_memory(&PathBuf::from(format!("/proc/{child}/")))?; // and the child is at the same place
+    child_memory.write_all_at(&code, regs.rip).map_err(|_| ())?;
+    set_registers(child, regs)?;
+    Ok(child)
+}
+
+pub fn call_function(mut call: Call) -> Result<CallResult, String> { // runs a function in the selected thread (the others stay stopped), all of its registers are restored afterwards
+    if CORE.access().is_some() {
+        return Err("Functions cannot be called in a core dump".to_string());
+    }
+    let pid = PID.access().ok_or("The program is not running")?;
+    let tid = current_thread();
+    let regs = get_registers(tid).map_err(|_| "Could not read the registers")?;
+    let fpregs = ptrace::getregset::<ptrace::regset::NT_PRFPREG>(tid).map_err(|_| "Could not read the floating point registers")?;
+    let entry = auxv_entry(&process_auxv(pid), nix::libc::AT_ENTRY).ok_or("Could not find the entry point for the return")?;
+
+    let size = match call.returns {
+        CallReturn::Struct(size, _) | CallReturn::Memory(size) => size,
+        CallReturn::Registers => 0
+    };
+    let memory = (regs.rsp - RED_ZONE - size) & !15; // the result, the arguments and the return address go below the red zone
+    if let CallReturn::Memory(_) = call.returns {
+        call.integers.insert(0, memory);
+    }
+    let arguments = (memory - call.stack.len() as u64 * 8) & !15; // the stack is aligned at the call
+    let mut stack: Vec<u8> = entry.to_le_bytes().to_vec();
+    stack.extend(call.stack.iter().flat_map(|word| word.to_le_bytes()));
+
+    let mut new = regs;
+    new.rip = call.address;
+    new.rsp = arguments - 8;
+    new.orig_rax = u64::MAX; // no syscall restart
+    new.rax = call.floats.len() as u64; // for the variadic functions
+    let mut registers = [&mut new.rdi, &mut new.rsi, &mut new.rdx, &mut new.rcx, &mut new.r8, &mut new.r9];
+    for (register, value) in registers.iter_mut().zip(call.integers.iter()) {
+        **register = *value;
+    }
+    let mut new_fp = fpregs;
+    for (i, value) in call.floats.iter().enumerate() {
+        new_fp.xmm_space[i*4] = *value as u32;
+        new_fp.xmm_space[i*4 + 1] = (*value >> 32) as u32;
+        new_fp.xmm_space[i*4 + 2] = 0;
+        new_fp.xmm_space[i*4 + 3] = 0;
+    }
+
+    let bind = MEMORY.access();
+    let file = bind.as_ref().ok_or("The memory of the program is not open")?;
+    let mut byte = [0u8];
+    file.read_exact_at(&mut byte, entry).map_err(|_| "Could not read the entry point")?;
+    file.write_all_at(&stack, new.rsp).map_err(|_| "Could not write the arguments on the stack")?;
+    file.write_all_at(&[0xcc], entry).map_err(|_| "Could not insert the return breakpoint")?;
+    drop(bind);
+
+    let result = (|| {
+        set_registers(tid, new).map_err(|_| "Could not set the registers")?;
+        ptrace::setregset::<ptrace::regset::NT_PRFPREG>(tid, new_fp).map_err(|_| "Could not set the floating point registers")?;
+        ptrace::cont(tid, None).map_err(|_| "Could not run the function")?;
+        let start = std::time::Instant::now();
+        let status = loop {
+            match call_wait(pid, tid, start) {
+                Some(wait::WaitStatus::PtraceEvent(_, _, event)) if event == ptrace::Event::PTRACE_EVENT_CLONE as i32 => { // the new thread stays stopped with the others
+                    call_clone(tid);
+                    ptrace::cont(tid, None).map_err(|_| "Could not run the function")?;
+                },
+                Some(wait::WaitStatus::PtraceEvent(_, _, event)) if event == ptrace::Event::PTRACE_EVENT_FORK as i32 || event == ptrace::Event::PTRACE_EVENT_VFORK as i32 => {
+                    if let Ok(child) = ptrace::getevent(tid).map(|child| Pid::from_raw(child as i32)) { // the child would run the rest of the call on its own
+                        let _ = wait(child);
+                        let _ = ptrace::detach(child, Signal::SIGKILL);
+                    }
+                    return Err("The call forked a new process, the process was killed and the call stopped".to_string());
+                },
+                status => break status
+            }
+        };
+        match status {
+            Some(wait::WaitStatus::Stopped(_, Signal::SIGTRAP)) if get_registers(tid).is_ok_and(|after| after.rip == entry + 1) => (),
+            Some(wait::WaitStatus::Stopped(_, signal)) => return Err(format!("The call was interrupted by {}", signal)), // the signal is discarded
+            Some(wait::WaitStatus::Exited(..) | wait::WaitStatus::Signaled(..)) => return Err("The program ended during the call".to_string()),
+            Some(status) => return Err(format!("The call was interrupted ({:?})", status)),
+            None => return Err(format!("The call did not return in {} seconds", CALL_TIMEOUT.as_secs()))
+        }
+
+        let after = get_registers(tid).map_err(|_| "Could not read the result")?;
+        let after_fp = ptrace::getregset::<ptrace::regset::NT_PRFPREG>(tid).map_err(|_| "Could not read the result")?;
+        let xmm: Vec<u8> = after_fp.xmm_space[..8].iter().flat_map(|word| word.to_le_bytes()).collect();
+        if let CallReturn::Struct(size, sse) = &call.returns { // the parts of the struct from the registers, put together in the memory
+            let (mut integers, mut floats) = ([after.rax, after.rdx].into_iter(), [&xmm[..8], &xmm[16..24]].into_iter());
+            let mut bytes: Vec<u8> = sse.iter().flat_map(|sse| if *sse {
+                floats.next().unwrap().to_vec()
+            } else {
+                integers.next().unwrap().to_le_bytes().to_vec()
+            }).collect();
+            bytes.truncate(*size as usize);
+            MEMORY.access().as_ref().ok_or("The memory of the program is not open")?.write_all_at(&bytes, memory).map_err(|_| "Could not save the result")?;
+        }
+        Ok(CallResult {rax: after.rax, xmm0: xmm[..16].try_into().unwrap(), memory})
+    })();
+
+    if let Some(file) = MEMORY.access().as_ref() { // restoring the thread as it was before the call
+        let _ = file.write_all_at(&byte, entry);
+    }
+    let _ = set_registers(tid, regs);
+    let _ = ptrace::setregset::<ptrace::regset::NT_PRFPREG>(tid, fpregs);
+    result
+}
+
+fn call_clone(tid: Pid) { // a thread created by the called function, it waits for the next continue like the other threads
+    let new = match ptrace::getevent(tid) {
+        Ok(new) => Pid::from_raw(new as i32),
+        Err(_) => return
+    };
+    let _ = wait(new); // its starting SIGSTOP
+    let process = thread_process(tid);
+    if process == PID.access().unwrap() && WATCHPOINTS.access().is_some() { // debug registers are not inherited
+        let _ = apply_watchpoints(new);
+    }
+    THREADS.access().as_mut().unwrap().insert(new, Thread::new(process));
+}
+
+fn call_wait(pid: Pid, tid: Pid, start: std::time::Instant) -> Option<wait::WaitStatus> { // the next stop of the called function, None if it runs for too long (then it gets stopped)
+    while start.elapsed() < CALL_TIMEOUT {
+        match wait::waitpid(tid, Some(wait::WaitPidFlag::__WALL | wait::WaitPidFlag::WNOHANG)) {
+            Ok(wait::WaitStatus::StillAlive) => std::thread::sleep(std::time::Duration::from_millis(1)),
+            Ok(status) => return Some(status),
+            Err(_) => return Some(wait::WaitStatus::Exited(tid, -1))
+        }
+    }
+    let _ = stop_thread(pid, tid);
+    let _ = wait(tid);
+    None
+}
+
 fn set_registers(pid: Pid, regs: user_regs_struct) -> Result<(), ()> { // wrapper for PTRACE_SETREGS
     match ptrace::setregs(pid, regs) {
         Ok(()) => Ok(()),
@@ -776,6 +2713,12 @@ fn set_registers(pid: Pid, regs: user_regs_struct) -> Result<(), ()> { // wrappe
 
 fn kill_tracee(pid: Pid) -> Result<(), ()> { // wrapper for PTRACE_KILL
     close_memory();
+    let inferiors: Vec<Pid> = INFERIORS.access().as_ref().map(|inferiors| inferiors.keys().copied().collect()).unwrap_or_default();
+    for other in inferiors { // the forked processes we follow end with the tracee
+        if other != pid {
+            let _ = ptrace::kill(other);
+        }
+    }
     match ptrace::kill(pid) {
         Ok(()) => (),
         Err(err) => {Dialog::error(&format!("Could not stop the tracee: {}", err), Some("Trace error")); return Err(());}
@@ -784,8 +2727,64 @@ fn kill_tracee(pid: Pid) -> Result<(), ()> { // wrapper for PTRACE_KILL
     Ok(())
 }
 
+fn attach_tracee(pid: Pid) -> Result<(), ()> { // wrapper for PTRACE_ATTACH (the tracee gets stopped by SIGSTOP)
+    match ptrace::attach(pid) {
+        Ok(()) => Ok(()),
+        Err(err) => {Dialog::error(&format!("Could not attach to the process {}: {}\n(Attaching to processes that are not our children might be restricted by /proc/sys/kernel/yama/ptrace_scope)", pid, err), Some("Trace error")); Err(())}
+    }
+}
+
+fn detach_tracee(pid: Pid) -> Result<(), ()> { // wrapper for PTRACE_DETACH, the process keeps running without us
+    close_memory();
+    let threads: Vec<(Pid, Thread)> = THREADS.access().as_ref().map(|threads| threads.clone().into_iter().collect()).unwrap_or_default();
+    let mut signal = None;
+    for (tid, thread) in threads {
+        if thread.stop_pending && ptrace::cont(tid, None).is_ok() { // our SIGSTOP would stop the process after we leave, so we consume it first
+            let _ = wait(tid);
+        }
+        if WATCHPOINTS.access().is_some() { // the armed debug registers would trap the process after we leave, DR7 first
+            for index in [DR7, 0, 1, 2, 3] {
+                let _ = set_debug_register(tid, index, 0);
+            }
+        }
+        if tid == pid {
+            signal = thread.signal;
+            continue;
+        }
+        let _ = ptrace::detach(tid, thread.signal);
+    }
+    WATCHPOINTS.none();
+    match ptrace::detach(pid, signal) {
+        Ok(()) => Ok(()),
+        Err(err) => {Dialog::error(&format!("Could not detach from the tracee: {}", err), Some("Trace error")); Err(())}
+    }
+}
+
+fn get_debug_register(tid: Pid, index: usize) -> Result<u64, ()> { // wrapper for PTRACE_PEEKUSER
+    ptrace::read_user(tid, (DEBUG_REGISTERS + index * 8) as ptrace::AddressType).map(|value| value as u64).map_err(|_| ())
+}
+
+fn set_debug_register(tid: Pid, index: usize, value: u64) -> Result<(), ()> { // wrapper for PTRACE_POKEUSER
+    ptrace::write_user(tid, (DEBUG_REGISTERS + index * 8) as ptrace::AddressType, value as i64).map_err(|_| ())
+}
+
+fn set_options(pid: Pid) -> Result<(), ()> { // wrapper for PTRACE_SETOPTIONS
+    let options = ptrace::Options::PTRACE_O_TRACECLONE | ptrace::Options::PTRACE_O_TRACEFORK | ptrace::Options::PTRACE_O_TRACEVFORK | ptrace::Options::PTRACE_O_TRACEEXEC | ptrace::Options::PTRACE_O_TRACESYSGOOD; // the syscall stops are told apart from the SIGTRAPs
+    match ptrace::setoptions(pid, options) {
+        Ok(()) => Ok(()),
+        Err(err) => {Dialog::error(&format!("Could not set the trace options: {}", err), Some("Trace error")); Err(())}
+    }
+}
+
+fn stop_thread(pid: Pid, tid: Pid) -> Result<(), ()> { // tgkill(pid, tid, SIGSTOP), stopping a single thread
+    match unsafe {nix::libc::syscall(nix::libc::SYS_tgkill, pid.as_raw(), tid.as_raw(), nix::libc::SIGSTOP)} {
+        0 => Ok(()),
+        _ => Err(())
+    }
+}
+
 fn restart_tracee(pid: Pid, signal: Option<Signal>) -> Result<(), ()> { // wrapper for PTRACE_CONT, also used for signaling the tracee,
-    match ptrace::cont(pid, signal) {
+    match resume(pid, signal) {
         Ok(()) => Ok(()),
         Err(err) => {Dialog::error(&format!("Could not deliver the signal to the tracee: {}", err), Some("Trace error")); Err(())}
     }
@@ -822,7 +2821,10 @@ pub fn test_memory(address: u64) -> Result<(), bool> { // tests if the address i
     Ok(())
 }
 
-pub fn read_memory(address: u64, amount: usize) -> Result<Vec<u8>, ()> { // reads from the memory file
+pub fn read_memory(address: u64, amount: usize) -> Result<Vec<u8>, ()> { // reads from the memory file (or the core)
+    if let Some(core) = CORE.access().as_ref() {
+        return core.read(address, amount);
+    }
     let mut bind = MEMORY.access();
     let mut memory = bind.as_mut().unwrap();
     let mut buf: Vec<u8> = vec![0; amount];
@@ -837,9 +2839,47 @@ pub fn read_memory(address: u64, amount: usize) -> Result<Vec<u8>, ()> { // read
     Ok(buf)
 }
 
+fn write_memory(state: &mut window::State, address: u64, mut bytes: Vec<u8>) -> Result<(), ()> { // writes into the memory file, works even while running and in the read-only code
+    if CORE.access().is_some() {
+        Dialog::error("The memory of a core dump cannot be changed.", Some("Memory error"));
+        return Err(());
+    }
+    if let Some(breakpoints) = BREAKPOINTS.access().as_mut() {
+        for (key, breakpoint) in breakpoints.iter_mut().filter(|(_, breakpoint)| breakpoint.inserted) {
+            shadow_byte(address, &mut bytes, anti_normal(*key), &mut breakpoint.byte);
+        }
+    }
+    if let Some(breakpoints) = state.internal.source_step.as_mut() {
+        for (key, breakpoint) in breakpoints.iter_mut().filter(|(_, breakpoint)| breakpoint.inserted) {
+            shadow_byte(address, &mut bytes, anti_normal(*key), &mut breakpoint.byte);
+        }
+    }
+    if let Some(Linker {address: linker, byte: Some(byte), ..}) = LINKER.access().as_mut() {
+        shadow_byte(address, &mut bytes, *linker, byte);
+    }
+
+    let mut bind = MEMORY.access();
+    let memory = match bind.as_mut() {
+        Some(memory) => memory,
+        None => return Err(())
+    };
+    seek_memory(address, memory)?;
+    match memory.write_all(&bytes) {
+        Ok(()) => Ok(()),
+        Err(err) => {Dialog::error(&format!("Could not write into the memory file: {}", err), Some("Memory error")); Err(())}
+    }
+}
+
+fn shadow_byte(address: u64, bytes: &mut [u8], breakpoint: u64, byte: &mut u8) { // a write over an inserted 0xCC changes only the saved byte, the breakpoint stays in the memory
+    if let Some(offset) = breakpoint.checked_sub(address) && (offset as usize) < bytes.len() {
+        *byte = bytes[offset as usize];
+        bytes[offset as usize] = 0xcc;
+    }
+}
+
 
-pub fn wait(pid: Pid) -> Result<wait::WaitStatus, nix::errno::Errno> { // waits for the next signal
-    wait::waitpid(pid, None)
+pub fn wait(pid: Pid) -> Result<wait::WaitStatus, nix::errno::Errno> { // waits for the next signal (__WALL for the threads)
+    wait::waitpid(pid, Some(wait::WaitPidFlag::__WALL))
 }
 
 pub async fn wait_async(pid: Pid) -> Result<wait::WaitStatus, nix::errno::Errno> { // async wrapper for wait
@@ -847,6 +2887,15 @@ pub async fn wait_async(pid: Pid) -> Result<wait::WaitStatus, nix::errno::Errno>
 }
 
 
+fn get_syscall_info(tid: Pid) -> Result<nix::libc::ptrace_syscall_info, ()> { // wrapper for PTRACE_GET_SYSCALL_INFO (nix passes no size, so the kernel would copy nothing)
+    let mut info = std::mem::MaybeUninit::<nix::libc::ptrace_syscall_info>::zeroed();
+    let size = std::mem::size_of::<nix::libc::ptrace_syscall_info>();
+    match unsafe {nix::libc::ptrace(nix::libc::PTRACE_GET_SYSCALL_INFO, tid.as_raw(), size, info.as_mut_ptr())} {
+        -1 => Err(()),
+        _ => Ok(unsafe {info.assume_init()})
+    }
+}
+
 fn get_sig_info(pid: Pid) -> Result<nix::libc::siginfo_t, ()> { // wrapper for PTRACE_GETSIGINFO
     match ptrace::getsiginfo(pid) {
         Ok(info) => Ok(info),
@@ -861,3 +2910,172 @@ fn send_signal(pid: Pid, signal: Signal) -> Result<(), ()> { // wrapper for kill
 fn test_pid(pid: Pid) -> bool { // testing if the program with PID is still alive
     signal::kill(pid, None).is_ok()
 }
+
+
+// Core Dumps
+
+const PRSTATUS_PID: usize = 32; // offsets into the elf_prstatus of x86_64
+const PRSTATUS_REGS: usize = 112;
+
+impl Core {
+    fn parse(path: PathBuf) -> Result<Self, &'static str> { // the PT_LOAD segments hold the memory, the PT_NOTE the threads, mapped files and the auxiliary vector
+        use object_foreign::read::elf::{FileHeader, ProgramHeader};
+        use object_foreign::elf;
+
+        let data = std::fs::read(&path).map_err(|_| "Could not read the core file.")?;
+        let header = elf::FileHeader64::<object_foreign::Endianness>::parse(&*data).map_err(|_| "The file is not a 64-bit ELF file.")?;
+        let endian = header.endian().map_err(|_| "The file is not a 64-bit ELF file.")?;
+        if header.e_type(endian) != elf::ET_CORE {
+            return Err("The file is not a core dump.");
+        }
+
+        let mut core = Core {
+            path,
+            pid: Pid::from_raw(0),
+            signal: None,
+            threads: Vec::new(),
+            fpregs: HashMap::new(),
+            auxv: Vec::new(),
+            segments: Vec::new(),
+            files: Vec::new(),
+            data: Vec::new()
+        };
+        for segment in header.program_headers(endian, &*data).map_err(|_| "Could not read the segments of the core.")? {
+            match segment.p_type(endian) {
+                elf::PT_LOAD => core.segments.push(CoreSegment {
+                    range: segment.p_vaddr(endian)..segment.p_vaddr(endian) + segment.p_memsz(endian),
+                    offset: segment.p_offset(endian),
+                    size: segment.p_filesz(endian),
+                    flags: segment.p_flags(endian)
+                }),
+                elf::PT_NOTE => {
+                    let mut notes = match segment.notes(endian, &*data) {
+                        Ok(Some(notes)) => notes,
+                        _ => continue
+                    };
+                    while let Ok(Some(note)) = notes.next() {
+                        match (note.name(), note.n_type(endian)) { // the extended registers are under the LINUX name
+                            (b"CORE", elf::NT_PRSTATUS) => core.prstatus(note.desc()),
+                            (b"CORE", elf::NT_FPREGSET) => core.fpregset(note.desc()),
+                            (b"LINUX", elf::NT_X86_XSTATE) => core.xstate(note.desc()),
+                            (b"CORE", elf::NT_AUXV) => core.auxv = note.desc().to_vec(),
+                            (b"CORE", elf::NT_FILE) => core.mapped_files(note.desc()),
+                            _ => ()
+                        }
+                    }
+                },
+                _ => ()
+            }
+        }
+        if core.threads.is_empty() {
+            return Err("The core dump does not contain any threads.");
+        }
+        core.data = data;
+        Ok(core)
+    }
+
+    fn prstatus(&mut self, desc: &[u8]) { // a thread with its registers
+        if desc.len() < PRSTATUS_REGS + std::mem::size_of::<user_regs_struct>() {
+            return;
+        }
+        let tid = Pid::from_raw(i32::from_le_bytes(desc[PRSTATUS_PID..PRSTATUS_PID + 4].try_into().unwrap()));
+        let regs = unsafe { // the pr_reg has the same layout
+            std::ptr::read_unaligned(desc[PRSTATUS_REGS..].as_ptr() as *const user_regs_struct)
+        };
+        if self.threads.is_empty() { // the first thread is the one that received the signal
+            self.pid = tid;
+            self.signal = Signal::try_from(i16::from_le_bytes(desc[12..14].try_into().unwrap()) as i32).ok(); // pr_cursig
+        }
+        self.threads.push((tid, regs));
+    }
+
+    fn fpregset(&mut self, desc: &[u8]) { // the FXSAVE area of the last thread
+        let tid = match self.threads.last() {
+            Some((tid, _)) => *tid,
+            None => return
+        };
+        if let Some(fpregs) = fxsave_registers(desc) {
+            self.fpregs.insert(tid, fpregs);
+        }
+    }
+
+    fn xstate(&mut self, desc: &[u8]) { // the XSAVE area of the last thread, its start is the same FXSAVE area
+        let tid = match self.threads.last() {
+            Some((tid, _)) => *tid,
+            None => return
+        };
+        if let Some(mut fpregs) = fxsave_registers(desc) {
+            fpregs.ymm = xstate_ymm(desc);
+            self.fpregs.insert(tid, fpregs);
+        }
+    }
+
+    fn mapped_files(&mut self, desc: &[u8]) { // count and page size, then the ranges with their page offsets, then the names
+        let word = |index: usize| desc.get(index * 8..index * 8 + 8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+        let (count, page) = match (word(0), word(1)) {
+            (Some(count), Some(page)) => (count as usize, page),
+            _ => return
+        };
+        let mut names = desc.get((2 + count * 3) * 8..).unwrap_or_default().split(|byte| *byte == 0);
+        for index in 0..count {
+            let (start, end, offset) = match (word(2 + index * 3), word(3 + index * 3), word(4 + index * 3)) {
+                (Some(start), Some(end), Some(offset)) => (start, end, offset),
+                _ => return
+            };
+            let name = names.next().map(|name| String::from_utf8_lossy(name).into_owned()).unwrap_or_default();
+            self.files.push((start..end, offset * page, name));
+        }
+    }
+
+    fn executable(&self) -> Option<&str> { // the mapped file with the entry point
+        let entry = auxv_entry(&self.auxv, nix::libc::AT_ENTRY)?;
+        self.files.iter().find(|(range, ..)| range.contains(&entry)).map(|(.., name)| name.as_str())
+    }
+
+    fn maps(&self) -> Vec<MemoryMap> { // the segments named like in the proc_fs maps
+        let rsp = self.threads[0].1.rsp;
+        self.segments.iter().map(|segment| {
+            let file = self.files.iter().find(|(range, ..)| range.start == segment.range.start);
+            MemoryMap {
+                name: match file {
+                    Some((.., name)) => name.clone(),
+                    None if segment.range.contains(&rsp) => "[stack]".to_string(),
+                    None => "[anon]".to_string()
+                },
+                range: segment.range.clone(),
+                _permissions: MapBits {
+                    _r: segment.flags & object_foreign::elf::PF_R != 0,
+                    _w: segment.flags & object_foreign::elf::PF_W != 0,
+                    _x: segment.flags & object_foreign::elf::PF_X != 0,
+                    _p: true
+                },
+                offset: file.map_or(0, |(_, offset, _)| *offset)
+            }
+        }).collect()
+    }
+
+    fn read(&self, mut address: u64, amount: usize) -> Result<Vec<u8>, ()> { // from the dumped segments, or from the mapped files for the parts that were not dumped
+        let mut buf = Vec::with_capacity(amount);
+        while buf.len() < amount {
+            let segment = self.segments.iter().find(|segment| segment.range.contains(&address)).ok_or(())?;
+            let inside = address - segment.range.start;
+            let left = (amount - buf.len()) as u64;
+            if inside < segment.size {
+                let length = left.min(segment.size - inside);
+                let start = (segment.offset + inside) as usize;
+                buf.extend_from_slice(self.data.get(start..start + length as usize).ok_or(())?);
+                address += length;
+                continue;
+            }
+            let length = left.min(segment.range.end - address);
+            let (range, offset, name) = self.files.iter().find(|(range, ..)| range.contains(&address)).ok_or(())?;
+            let mut file = File::open(name).map_err(|_| ())?;
+            file.seek(std::io::SeekFrom::Start(offset + address - range.start)).map_err(|_| ())?;
+            let mut bytes = vec![0; length as usize];
+            file.read_exact(&mut bytes).map_err(|_| ())?;
+            buf.extend_from_slice(&bytes);
+            address += length;
+        }
+        Ok(buf)
+    }
+}
@@ -37,6 +37,7 @@ use crate::{
     data::*,
     trace::*,
     dwarf::*,
+    eval,
     style,
     config,
     object
@@ -52,6 +53,12 @@ const BOLD: font::Font = font::Font {weight: font::Weight::Bold, ..font::Font::D
 // PaneGrid Layout
 const SIDERATIO: f32 = 0.25; // (0.1; 0.4)      // Default ratio of sidebars
 
+// Syscalls
+const SYSCALLS_SHOWN: usize = 500; // newest log entries in the pane, more text widgets slow the view down
+
+// Log
+const LOGS_SHOWN: usize = 500; // newest logpoint messages in the pane
+
 pub struct Layout { // state of the Mainframe
     status_bar: bool,
     sidebar_left: bool,
@@ -64,7 +71,12 @@ pub struct Layout { // state of the Mainframe
 
 impl Default for Layout {
     fn default() -> Self {
-        let layout = CONFIG.access().as_ref().unwrap().layout.clone().unwrap(); // load from config
+        Self::new(CONFIG.access().as_ref().unwrap().layout.clone().unwrap()) // load from config
+    }
+}
+
+impl Layout {
+    pub fn new(layout: config::Layout) -> Self { // from the config, or from the session of the executable
         Layout {
             status_bar: *layout.status_bar.as_ref().unwrap(),
             sidebar_left: *layout.sidebar_left.as_ref().unwrap(),
@@ -75,6 +87,42 @@ impl Default for Layout {
             _focus: None
         }
     }
+
+    pub fn session_layout(&self) -> config::Layout { // the panes of each part in order, the hidden parts from the SAVED_STATE (the sizes are not kept)
+        let (main, left, right, panel) = self.get_nodes();
+        let saved = SAVED_STATE.access().clone().unwrap();
+        let list = |node: Option<pane_grid::Node>, saved: &pane_grid::Configuration<Pane>| {
+            let mut panes = Vec::new();
+            match node {
+                Some(node) => configuration_panes(&self.node_to_configuration(&node), &mut panes),
+                None => configuration_panes(saved, &mut panes)
+            }
+            panes
+        };
+        config::Layout {
+            status_bar: Some(self.status_bar),
+            sidebar_left: Some(self.sidebar_left),
+            sidebar_right: Some(self.sidebar_right),
+            panel: Some(self.panel),
+            panel_mode: Some(self.panel_mode),
+            panes: Some(config::Panes {
+                main: list(Some(main), &pane_grid::Configuration::Pane(Pane::_Empty)),
+                left: list(left.map(|node| node.0), &saved.left_sidebar.0),
+                right: list(right.map(|node| node.0), &saved.right_sidebar.0),
+                panel: list(panel.map(|node| node.0), &saved.panel.0)
+            })
+        }
+    }
+}
+
+fn configuration_panes(configuration: &pane_grid::Configuration<Pane>, panes: &mut Vec<config::Pane>) { // the panes of the configuration from the first to the last
+    match configuration {
+        pane_grid::Configuration::Pane(pane) => panes.extend(pane.config()),
+        pane_grid::Configuration::Split {a, b, ..} => {
+            configuration_panes(a, panes);
+            configuration_panes(b, panes);
+        }
+    }
 }
 
 impl Layout {
@@ -390,7 +438,12 @@ impl Layout {
                 config::Pane::stack => Pane::Stack(PaneStack::default()),
                 config::Pane::info => Pane::Info,
                 config::Pane::control => Pane::Control(PaneControl::default()),
-                config::Pane::terminal => Pane::Terminal(PaneTerminal::default())
+                config::Pane::terminal => Pane::Terminal(PaneTerminal::default()),
+                config::Pane::threads => Pane::Threads,
+                config::Pane::syscalls => Pane::Syscalls(PaneSyscalls::default()),
+                config::Pane::watch => Pane::Watch(PaneWatch::default()),
+                config::Pane::log => Pane::Log,
+                config::Pane::breakpoints => Pane::Breakpoints
             }
         };
         if list.is_empty() {
@@ -712,10 +765,33 @@ pub enum Pane { // Generic enum for all bars (completed widgets that can be move
     Terminal(PaneTerminal),
     Stack(PaneStack),
     Assembly(PaneAssembly),
+    Threads,
+    Syscalls(PaneSyscalls),
+    Watch(PaneWatch),
+    Log,
+    Breakpoints,
     _Empty
 }
 
 impl Pane { // to avoid matching when we know which pane we are working with
+    fn config(&self) -> Option<config::Pane> { // the name in the config
+        Some(match self {
+            Pane::Control(_) => config::Pane::control,
+            Pane::Registers(_) => config::Pane::registers,
+            Pane::Memory(_) => config::Pane::memory,
+            Pane::Code(_) => config::Pane::code,
+            Pane::Info => config::Pane::info,
+            Pane::Terminal(_) => config::Pane::terminal,
+            Pane::Stack(_) => config::Pane::stack,
+            Pane::Assembly(_) => config::Pane::assembly,
+            Pane::Threads => config::Pane::threads,
+            Pane::Syscalls(_) => config::Pane::syscalls,
+            Pane::Watch(_) => config::Pane::watch,
+            Pane::Log => config::Pane::log,
+            Pane::Breakpoints => config::Pane::breakpoints,
+            Pane::_Empty => return None
+        })
+    }
     fn memory(&mut self) -> &mut PaneMemory {
         match self {
             Pane::Memory(inner) => inner,
@@ -752,19 +828,52 @@ impl Pane { // to avoid matching when we know which pane we are working with
             _ => panic!()
         }
     }
+    fn syscalls(&mut self) -> &mut PaneSyscalls {
+        match self {
+            Pane::Syscalls(inner) => inner,
+            _ => panic!()
+        }
+    }
+    fn watch(&mut self) -> &mut PaneWatch {
+        match self {
+            Pane::Watch(inner) => inner,
+            _ => panic!()
+        }
+    }
 }
 
 // Each pane has its own struct (its state), and has a view() function that retrieves the graphics of the pane
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct PaneControl {
     selected_signal: Option<Signal>,
+    args: String, // launch fields, as typed by the user (parsed into the LAUNCH on every input)
+    env: String,
+    directory: String,
+    processes: Vec<object::Process>, // running processes to attach to (refreshed by the user)
+    selected_process: Option<object::Process>,
+    disposition_signal: Option<Signal> // signal selected in the disposition table
+}
+impl Default for PaneControl {
+    fn default() -> Self { // the launch fields start with the values from the config
+        let launch = LAUNCH.access().clone().unwrap();
+        Self {
+            selected_signal: None,
+            args: join_arguments(&launch.args.unwrap_or_default()),
+            env: join_arguments(&launch.env.unwrap_or_default()),
+            directory: launch.directory.unwrap_or_default(),
+            processes: object::list_processes(),
+            selected_process: None,
+            disposition_signal: None
+        }
+    }
 }
 impl PaneControl {
     fn view<'a>(&self, state: &'a State, id: pane_grid::Pane) -> Container<'a, Message> {
         let size = 30;
 
         // loading the states
-        let file = FILE.access().is_some();
+        let core = CORE.access().as_ref().map(|core| core.path.clone());
+        let file = FILE.access().is_some() && core.is_none(); // a core dump cant be run
         let run = PID.access().is_some();
         let stopped = state.internal.stopped;
 
@@ -806,6 +915,19 @@ impl PaneControl {
             } else {None}
         ).style(style::widget_button);
 
+        let scope_step = |icon, operation| svg_button( // next and finish, same rules as the source step
+            icon,
+            size,
+            Some(
+                if stopped & !state.internal.no_debug & state.last_signal.is_none() {style::widget_svg}
+                else {style::button_svg_disabled}
+            )
+        ).on_press_maybe(
+            if stopped & !state.internal.no_debug & state.last_signal.is_none() {Some(Message::Operation(operation))} else {None}
+        ).style(style::widget_button);
+        let step_over = scope_step("icons/step_over.svg", Operation::StepOver);
+        let step_out = scope_step("icons/step_out.svg", Operation::StepOut);
+
         // kill and signal buttons just set the last signal to the desired signal, delivered on continue or step
         let kill = svg_button("icons/signal_kill.svg", size, Some(if run {style::widget_svg} else {style::button_svg_disabled}))
         .on_press_maybe(if run {Some(Message::Operation(Operation::Kill))} else {None})
@@ -836,25 +958,230 @@ impl PaneControl {
         let select = pick_list(signals, self.selected_signal, move |signal| Message::Pane(PaneMessage::ControlSelectSignal(id, signal)))
         .placeholder("Signal...");
 
-        let content = container(row![ // row of buttons
+        let buttons = row![ // row of buttons
             start_stop,
             pause_cont,
             step,
             source_step,
+            step_over,
+            step_out,
             kill,
             signal,
             select
-        ].padding(3)).style(style::back).width(Length::Fill);
+        ].padding(3);
+
+        let content = container(scrollable(column![
+            buttons,
+            self.attach(id, size, run || core.is_some()),
+            self.core(size, run, core.clone()),
+            self.record(state, size, run && core.is_none()),
+            self.checkpoints(state, size, run && core.is_none()),
+            self.launch(id, size),
+            self.signals(state, id, size)
+        ]).width(Length::Fill)).style(style::back).width(Length::Fill);
         content
-}
+    }
+
+    fn attach<'a>(&self, id: pane_grid::Pane, size: u16, run: bool) -> Row<'a, Message> { // process picker for attaching to a running process
+        let refresh = svg_button("icons/reload_file.svg", size - 6, Some(if run {style::button_svg_disabled} else {style::widget_svg}))
+        .on_press_maybe(if run {None} else {Some(Message::Pane(PaneMessage::ControlRefreshProcesses(id)))})
+        .style(style::widget_button);
+
+        let select = pick_list(self.processes.clone(), self.selected_process.clone(), move |process| Message::Pane(PaneMessage::ControlSelectProcess(id, process)))
+        .placeholder("Process...")
+        .text_size(size - 12)
+        .width(Length::Fill);
+
+        let attach = !run && self.selected_process.is_some();
+        let button_attach: button::Button<'_, Message> = button(
+            text("Attach").center().size(size - 14).style(style::widget_text)
+        ).height(size - 6)
+        .style(style::widget_button)
+        .on_press_maybe(if attach {Some(Message::Operation(Operation::Attach(self.selected_process.as_ref().unwrap().pid)))} else {None});
+
+        row![
+            text("Attach:").size(size - 12).center().height(size).width(95),
+            select,
+            refresh,
+            button_attach
+        ].spacing(5).padding(padding::Padding {left: 5., right: 15., bottom: 3., top: 0.})
+    }
+
+    fn core<'a>(&self, size: u16, run: bool, core: Option<std::path::PathBuf>) -> Row<'a, Message> { // loading a core dump instead of running the program
+        let name = core.as_ref().and_then(|path| path.file_name()).map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+        let button_load: button::Button<'_, Message> = button(
+            text("Load core").center().size(size - 14).style(style::widget_text)
+        ).height(size - 6)
+        .style(style::widget_button)
+        .on_press_maybe(if !run && core.is_none() {Some(Message::Operation(Operation::LoadCore))} else {None});
+
+        let button_close: button::Button<'_, Message> = button(
+            text("Close").center().size(size - 14).style(style::widget_text)
+        ).height(size - 6)
+        .style(style::widget_button)
+        .on_press_maybe(if core.is_some() {Some(Message::Operation(Operation::CloseCore))} else {None});
+
+        row![
+            text("Core:").size(size - 12).center().height(size).width(95),
+            text(name).size(size - 12).center().height(size).width(Length::Fill),
+            button_load,
+            button_close
+        ].spacing(5).padding(padding::Padding {left: 5., right: 15., bottom: 3., top: 0.})
+    }
+
+    fn record<'a>(&self, state: &'a State, size: u16, run: bool) -> Row<'a, Message> { // recording the steps, so they can be taken back
+        let (on, history) = RECORD.access().as_ref().map_or((false, 0), |record| (record.on, record.history.len()));
+        let reverse = run && state.internal.stopped && history > 0;
+
+        let button_record: button::Button<'_, Message> = button(
+            text("Record").center().size(size - 14)
+            .style(if on {style::widget_text_toggled} else {style::widget_text})
+        ).height(size - 6)
+        .style(if on {style::widget_button_toggled} else {style::widget_button})
+        .on_press(Message::Operation(Operation::Record(!on)));
+
+        let button_reverse = |name, operation| button(text(name).center().size(size - 14).style(style::widget_text))
+        .height(size - 6)
+        .style(style::widget_button)
+        .on_press_maybe(if reverse {Some(Message::Operation(operation))} else {None});
+
+        row![
+            text("Record:").size(size - 12).center().height(size).width(95),
+            button_record,
+            text(format!("{} steps", history)).size(size - 12).center().height(size).width(Length::Fill),
+            button_reverse("Step back", Operation::ReverseStep),
+            button_reverse("Line back", Operation::ReverseSourceStep),
+            button_reverse("Continue back", Operation::ReverseContinue)
+        ].spacing(5).padding(padding::Padding {left: 5., right: 15., bottom: 3., top: 0.})
+    }
+
+    fn checkpoints<'a>(&self, state: &'a State, size: u16, run: bool) -> iced::widget::Column<'a, Message> { // the forked copies of the tracee, restoring one continues from its stop
+        let stopped = run && state.internal.stopped;
+        let checkpoints = CHECKPOINTS.access().clone().unwrap_or_default();
+
+        let button_checkpoint: button::Button<'_, Message> = button(
+            text("Checkpoint").center().size(size - 14).style(style::widget_text)
+        ).height(size - 6)
+        .style(style::widget_button)
+        .on_press_maybe(if stopped {Some(Message::Operation(Operation::Checkpoint))} else {None});
+
+        let list = column(checkpoints.into_iter().enumerate().map(|(i, checkpoint)| row![
+            text(format!("{}: {}", i, checkpoint.label)).size(size - 12).center().height(size - 6).width(Length::Fill),
+            button(text("Restore").center().size(size - 14).style(style::widget_text))
+            .height(size - 6)
+            .style(style::widget_button)
+            .on_press_maybe(if stopped {Some(Message::Operation(Operation::CheckpointRestore(i)))} else {None}),
+            button(text("Delete").center().size(size - 14).style(style::widget_text))
+            .height(size - 6)
+            .style(style::widget_button)
+            .on_press(Message::Operation(Operation::CheckpointRemove(i)))
+        ].spacing(5).padding(padding::Padding {left: 100., right: 0., bottom: 0., top: 0.}).into())).spacing(3);
+
+        column![
+            row![
+                text("Checkpoints:").size(size - 12).center().height(size).width(95),
+                button_checkpoint
+            ].spacing(5),
+            list
+        ].spacing(3).padding(padding::Padding {left: 5., right: 15., bottom: 3., top: 0.})
+    }
+
+    fn launch<'a>(&self, id: pane_grid::Pane, size: u16) -> iced::widget::Column<'a, Message> { // launch configuration fields, applied on the next run
+        let clear_env = LAUNCH.access().as_ref().unwrap().clear_env.unwrap_or(false);
+        let no_aslr = LAUNCH.access().as_ref().unwrap().no_aslr.unwrap_or(false);
+        let follow_fork = LAUNCH.access().as_ref().unwrap().follow_fork;
+        let stop_at = LAUNCH.access().as_ref().unwrap().stop_at;
+
+        let field = |name: &'a str, placeholder: &'a str, value: &str, message: fn(pane_grid::Pane, String) -> PaneMessage| row![
+            text(name).size(size - 12).center().height(size).width(95),
+            text_input(placeholder, value)
+            .on_input(move |data| Message::Pane(message(id, data)))
+            .on_paste(move |data| Message::Pane(message(id, data)))
+            .size(size - 12)
+            .line_height(iced::Pixels(size as f32 - 10.))
+            .width(Length::Fill)
+        ].spacing(5);
+
+        let button_clear: button::Button<'_, Message> = button(
+            text("Clear environment").center().size(size - 14)
+            .style(if clear_env {style::widget_text_toggled} else {style::widget_text})
+        ).height(size - 6)
+        .style(if clear_env {style::widget_button_toggled} else {style::widget_button})
+        .on_press(Message::Pane(PaneMessage::ControlLaunchClearEnv));
+
+        let button_aslr: button::Button<'_, Message> = button(
+            text("Disable ASLR").center().size(size - 14)
+            .style(if no_aslr {style::widget_text_toggled} else {style::widget_text})
+        ).height(size - 6)
+        .style(if no_aslr {style::widget_button_toggled} else {style::widget_button})
+        .on_press(Message::Pane(PaneMessage::ControlLaunchNoAslr));
+
+        let follow = row![
+            text("Follow fork:").size(size - 12).center().height(size).width(95),
+            pick_list([config::FollowFork::parent, config::FollowFork::child, config::FollowFork::both], follow_fork, |follow| Message::Pane(PaneMessage::ControlLaunchFollowFork(follow)))
+            .text_size(size - 12)
+        ].spacing(5);
+
+        let stop = row![
+            text("Stop at:").size(size - 12).center().height(size).width(95),
+            pick_list([config::StopAt::none, config::StopAt::entry, config::StopAt::main], stop_at, |stop| Message::Pane(PaneMessage::ControlLaunchStopAt(stop)))
+            .text_size(size - 12)
+        ].spacing(5);
+
+        column![
+            field("Arguments:", "--flag value...", &self.args, PaneMessage::ControlLaunchArgs),
+            field("Environment:", "KEY=VALUE...", &self.env, PaneMessage::ControlLaunchEnv),
+            field("Directory:", "Working directory...", &self.directory, PaneMessage::ControlLaunchDirectory),
+            follow,
+            stop,
+            row![button_clear, button_aslr].spacing(5)
+        ].spacing(3).padding(padding::Padding {left: 5., right: 15., bottom: 5., top: 0.})
+    }
+
+    fn signals<'a>(&self, state: &'a State, id: pane_grid::Pane, size: u16) -> iced::widget::Column<'a, Message> { // the disposition table, what each signal does to the tracee
+        let signals: Vec<Signal> = Signal::iterator().filter(|signal| ![Signal::SIGKILL, Signal::SIGSTOP, Signal::SIGTRAP].contains(signal)).collect(); // these cant be caught (or are used by the debugger)
+        let dispositions = CONFIG.access().as_ref().unwrap().signals.clone().unwrap_or_default();
+
+        let disposition = self.disposition_signal.map(|signal| row(
+            [config::Disposition::stop, config::Disposition::print, config::Disposition::pass, config::Disposition::ignore].into_iter().map(|disposition| {
+                let toggled = config::disposition(signal) == disposition;
+                button(text(disposition.to_string()).center().size(size - 14).style(if toggled {style::widget_text_toggled} else {style::widget_text}))
+                .height(size - 6)
+                .style(if toggled {style::widget_button_toggled} else {style::widget_button})
+                .on_press(Message::Pane(PaneMessage::ControlDisposition(signal, disposition)))
+                .into()
+            })
+        ).spacing(5));
+
+        let select = row![
+            text("Signals:").size(size - 12).center().height(size).width(95),
+            pick_list(signals, self.disposition_signal, move |signal| Message::Pane(PaneMessage::ControlSelectDisposition(id, signal)))
+            .placeholder("Signal...")
+            .text_size(size - 12)
+        ].push_maybe(disposition).spacing(5);
+
+        let table = dispositions.iter().filter(|(_, disposition)| **disposition != config::Disposition::stop).map(|(signal, disposition)| format!("{} {}", signal, disposition.to_string().to_lowercase())).collect::<Vec<String>>().join(", ");
+        let log = &state.internal.pane.signals;
+
+        column![
+            select,
+            text(format!("Not stopping: {}", if table.is_empty() {"none"} else {&table})).size(size - 14).style(style::weak)
+        ].push_maybe(if log.is_empty() {None} else {Some( // the newest printed signals
+            text(format!("Printed: {}", log[log.len().saturating_sub(5)..].join(", "))).size(size - 14)
+        )}).spacing(3).padding(padding::Padding {left: 5., right: 15., bottom: 5., top: 0.})
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct PaneRegisters {
-    format: Base
+    format: Base,
+    edit: Option<(usize, String)>, // clicked register (its line) and the new value
+    vector: bool, // showing the x87 and vector registers below the general ones
+    lane: Lane // how the XMM and YMM registers are split
 }
 impl PaneRegisters {
-    fn view<'a>(&self, id: pane_grid::Pane) -> Container<'a, Message> {
+    fn view<'a>(&'a self, state: &'a State, id: pane_grid::Pane) -> Container<'a, Message> {
 
         fn flags(num: u64) -> String { // creating visual flags from set bits in the RFLAGS
             let of = if num & (1 << 11) != 0 {"|OF"} else {""};
@@ -910,106 +1237,179 @@ impl PaneRegisters {
         .style(if self.format == Base::Bin {style::widget_button_toggled} else {style::widget_button})
         .on_press(Message::Pane(PaneMessage::RegistersChangeFormat(id, Base::Bin)));
 
-        let regs = REGISTERS.access().clone();
-
-        let (reg, value) = match regs { // two lists of reg_names and reg_values
-            Some(regs) => ([
-                "RIP:",
-                "RAX:",
-                "RBX:",
-                "RCX:",
-                "RDX:",
-                "RSI:",
-                "RDI:",
-                "RBP:",
-                "RSP:",
-                "R8:",
-                "R9:",
-                "R10:",
-                "R11:",
-                "R12:",
-                "R13:",
-                "R14:",
-                "R15:",
-                "RFS:",
-                "CS:",
-                "SS:",
-                "DS:",
-                "ES:",
-                "FS:",
-                "GS:",
-                "FSB:",
-                "GSB:"
-            ], [
-            regs.rip,
-            regs.rax,
-            regs.rbx,
-            regs.rcx,
-            regs.rdx,
-            regs.rsi,
-            regs.rdi,
-            regs.rbp,
-            regs.rsp,
-            regs.r8,
-            regs.r9,
-            regs.r10,
-            regs.r11,
-            regs.r12,
-            regs.r13,
-            regs.r14,
-            regs.r15,
-            regs.eflags,
-            regs.cs,
-            regs.ss,
-            regs.ds,
-            regs.es,
-            regs.fs,
-            regs.gs,
-            regs.fs_base,
-            regs.gs_base
-            ]),
+        let button_vector: button::Button<'_, Message> = button(
+            text("FP").center().font(EXTRABOLD).size(size - 12)
+            .style(if self.vector {style::widget_text_toggled} else {style::widget_text})
+        ).padding(4)
+        .height(size)
+        .width(size)
+        .style(if self.vector {style::widget_button_toggled} else {style::widget_button})
+        .on_press(Message::Pane(PaneMessage::RegistersVector(id)));
+
+        let lanes = if self.vector {Some(
+            pick_list(Lane::ALL, Some(self.lane), move |lane| Message::Pane(PaneMessage::RegistersLane(id, lane)))
+            .text_size(size - 14)
+        )} else {None};
+
+        let mut regs = match *REGISTERS.access() {
+            Some(regs) => regs,
             None => return program_message("Start the program to display registers.") // message if None
         };
+        let editable = state.internal.stopped && CORE.access().is_none();
+        let fields = register_fields(&mut regs);
 
-        let mut counter = 0;
-
-        let reg_lines = column(reg.map(|name| //names
-            text(name).center().size(size - 12).wrapping(text::Wrapping::None).into()
+        let reg_lines = column(fields.iter().map(|(name, _)| //names
+            text(*name).center().size(size - 12).wrapping(text::Wrapping::None).into()
         ));
-        let value_lines = column(value.map(|num| // values
-            if counter == 17 { // display flags next to the RFLAGS register
-                counter += 1;
-                text(format!("{}   {}", self.format.form(num), flags(num)))
-                .center()
-                .size(size - 12)
-                .style(style::widget_text)
-                .wrapping(text::Wrapping::None)
-                .into()
+        let value_lines = column(fields.iter().enumerate().map(|(i, (_, num))| { // values, clicking one edits it
+            let num = **num;
+            let value = if i == RFLAGS { // display flags next to the RFLAGS register
+                format!("{}   {}", self.format.form(num), flags(num))
             } else {
-                counter += 1;
-                text(self.format.form(num))
+                self.format.form(num)
+            };
+            mouse_area(
+                text(value)
                 .center()
                 .size(size - 12)
-                .style(style::widget_text)
+                .style(if self.edit.as_ref().is_some_and(|(edit, _)| *edit == i) {style::widget_text_toggled} else {style::widget_text})
                 .wrapping(text::Wrapping::None)
-                .into()
+            ).on_press(Message::Pane(PaneMessage::RegistersEdit(id, i)))
+            .into()
+        })).clip(true);
+
+        let edit = self.edit.as_ref().map(|(index, value)| {
+            let small = 24;
+            let mut edit = column![row![
+                text(fields[*index].0).size(small - 10).center().height(small),
+                text_input("0x, 0o, 0b or decimal...", value)
+                .on_input(move |data| Message::Pane(PaneMessage::RegistersEditInput(id, data)))
+                .on_paste(move |data| Message::Pane(PaneMessage::RegistersEditInput(id, data)))
+                .on_submit_maybe(if editable {Some(Message::Pane(PaneMessage::RegistersEditApply(id)))} else {None})
+                .size(small - 10)
+                .line_height(iced::Pixels(small as f32 - 8.))
+                .width(Length::Fill),
+                button(text("Apply").center().size(small - 10).style(style::widget_text))
+                .height(small)
+                .style(style::widget_button)
+                .on_press_maybe(if editable {Some(Message::Pane(PaneMessage::RegistersEditApply(id)))} else {None}), // registers can be set only while stopped
+                button(text("Cancel").center().size(small - 10).style(style::widget_text))
+                .height(small)
+                .style(style::widget_button)
+                .on_press(Message::Pane(PaneMessage::RegistersEditCancel(id)))
+            ].spacing(5)];
+            if *index == RFLAGS { // the single bits of the flags flip right away
+                let eflags = *fields[RFLAGS].1;
+                edit = edit.push(row(FLAGS.iter().map(|(name, bit)| {
+                    let set = eflags & (1 << bit) != 0;
+                    button(text(*name).center().size(small - 10).style(if set {style::widget_text_toggled} else {style::widget_text}))
+                    .height(small)
+                    .style(if set {style::widget_button_toggled} else {style::widget_button})
+                    .on_press_maybe(if editable {Some(Message::Pane(PaneMessage::RegistersFlag(*bit)))} else {None})
+                    .into()
+                })).spacing(3));
             }
-        )).clip(true);
+            edit.spacing(3).padding(3)
+        });
+
 
+        let vector_lines = if self.vector {Some(match FPREGS.access().as_ref() { // the x87, SSE and AVX sections
+            Some(fpregs) => {
+                let mut lines: Vec<(String, String)> = Vec::new();
+                for (i, st) in fpregs.st.iter().enumerate() {
+                    lines.push((format!("ST{}:", i), format!("{}", extended_to_f64(st))));
+                }
+                lines.push((String::from("MXCSR:"), self.format.form(fpregs.mxcsr as u64)));
+                for (i, xmm) in fpregs.xmm.iter().enumerate() {
+                    lines.push((format!("XMM{}:", i), self.lane.form(xmm)));
+                }
+                if let Some(ymm) = &fpregs.ymm {
+                    for (i, (low, high)) in fpregs.xmm.iter().zip(ymm.iter()).enumerate() {
+                        lines.push((format!("YMM{}:", i), self.lane.form(&[low.as_slice(), high.as_slice()].concat())));
+                    }
+                }
+                row![
+                    column(lines.iter().map(|(name, _)| text(name.clone()).center().size(size - 12).wrapping(text::Wrapping::None).into())),
+                    column(lines.into_iter().map(|(_, value)| text(value).center().size(size - 12).wrapping(text::Wrapping::None).into()))
+                ].padding(5).spacing(10)
+            },
+            None => row![text("The floating point registers are not available.").size(size - 12).style(style::weak)].padding(5) // cores and failed reads
+        })} else {None};
 
         let content = container(column![
-            row![button_hex, button_dec, button_oct, button_bin].padding(3).spacing(3),
+            row![button_hex, button_dec, button_oct, button_bin, button_vector].push_maybe(lanes).padding(3).spacing(3)
+        ].push_maybe(edit).push(
             scrollable(
-                row![reg_lines, value_lines]
-                .padding(5).spacing(10)
+                column![row![reg_lines, value_lines]
+                .padding(5).spacing(10)].push_maybe(vector_lines)
             ).width(Length::Fill)
             .direction(scrollable::Direction::Both { vertical: scrollbar(), horizontal: no_scrollbar() })
-        ]).style(style::back);
+        )).style(style::back);
         content
     }
 
 }
 
+const RFLAGS: usize = 17; // its line in the register_fields
+const FLAGS: [(&str, u64); 9] = [("CF", 0), ("PF", 2), ("AF", 4), ("ZF", 6), ("SF", 7), ("TF", 8), ("IF", 9), ("DF", 10), ("OF", 11)]; // the bits of the RFLAGS
+
+fn register_fields(regs: &mut nix::libc::user_regs_struct) -> [(&'static str, &mut u64); 26] { // the lines of the Registers pane, for showing and editing them
+    [
+        ("RIP:", &mut regs.rip),
+        ("RAX:", &mut regs.rax),
+        ("RBX:", &mut regs.rbx),
+        ("RCX:", &mut regs.rcx),
+        ("RDX:", &mut regs.rdx),
+        ("RSI:", &mut regs.rsi),
+        ("RDI:", &mut regs.rdi),
+        ("RBP:", &mut regs.rbp),
+        ("RSP:", &mut regs.rsp),
+        ("R8:", &mut regs.r8),
+        ("R9:", &mut regs.r9),
+        ("R10:", &mut regs.r10),
+        ("R11:", &mut regs.r11),
+        ("R12:", &mut regs.r12),
+        ("R13:", &mut regs.r13),
+        ("R14:", &mut regs.r14),
+        ("R15:", &mut regs.r15),
+        ("RFS:", &mut regs.eflags),
+        ("CS:", &mut regs.cs),
+        ("SS:", &mut regs.ss),
+        ("DS:", &mut regs.ds),
+        ("ES:", &mut regs.es),
+        ("FS:", &mut regs.fs),
+        ("GS:", &mut regs.gs),
+        ("FSB:", &mut regs.fs_base),
+        ("GSB:", &mut regs.gs_base)
+    ]
+}
+
+fn extended_to_f64(bytes: &[u8; 10]) -> f64 { // the x87 80 bit format, explicit integer bit in the mantissa, loses the extra precision
+    let mantissa = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    let exponent = u16::from_le_bytes([bytes[8], bytes[9]]);
+    let sign = if exponent & 0x8000 != 0 {-1.} else {1.};
+    let exponent = (exponent & 0x7fff) as i32;
+    match exponent {
+        0x7fff if mantissa << 1 == 0 => sign * f64::INFINITY,
+        0x7fff => f64::NAN,
+        _ => sign * (mantissa as f64 / (1u64 << 63) as f64) * 2f64.powi(exponent - 16383)
+    }
+}
+
+fn parse_register(value: &str) -> Option<u64> { // in any of the bases, negative decimals in two's complement
+    let value = value.trim().replace('_', "");
+    if let Some(hex) = value.strip_prefix("0x") {
+        return u64::from_str_radix(hex, 16).ok();
+    }
+    if let Some(oct) = value.strip_prefix("0o") {
+        return u64::from_str_radix(oct, 8).ok();
+    }
+    if let Some(bin) = value.strip_prefix("0b") {
+        return u64::from_str_radix(bin, 2).ok();
+    }
+    value.parse::<u64>().ok().or_else(|| value.parse::<i64>().ok().map(|value| value as u64))
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct PaneMemory {
     pub field: String,
@@ -1019,11 +1419,20 @@ pub struct PaneMemory {
     data: Vec<u8>, // the 4KB of data
     more_bytes: bool, // 4 or 8
     format: ByteBase,
-    read_error: bool // if read error occurs, show a button to take the user back (resets the address to a correct map)
+    read_error: bool, // if read error occurs, show a button to take the user back (resets the address to a correct map)
+    watch: Option<(u64, u8, WatchKind)>, // right clicked byte, being set up as a watchpoint
+    edit: Option<MemoryEdit> // left clicked byte, a value being written at it
+}
+
+#[derive(Debug, Clone)]
+struct MemoryEdit {
+    address: u64,
+    kind: WriteKind,
+    value: String
 }
 impl PaneMemory {
-    fn view<'a>(&'a self, id: pane_grid::Pane) -> Container<'a, Message> {
-        if MEMORY.access().is_none() {
+    fn view<'a>(&'a self, state: &'a State, id: pane_grid::Pane) -> Container<'a, Message> {
+        if MEMORY.access().is_none() && CORE.access().is_none() {
             return program_message("Start the program to display memory.");
         };
 
@@ -1107,7 +1516,7 @@ impl PaneMemory {
             let data: &Vec<u8> = &self.data; // bytes
 
             let mut addresses: Vec<u64> = Vec::new();
-            let mut bytes: Vec<Vec<u8>> = if self.more_bytes { // creating the byte columns
+            let mut bytes: Vec<Vec<(u64, u8)>> = if self.more_bytes { // creating the byte columns
                 vec![Vec::new(); 8]
             } else {
                 vec![Vec::new(); 4]
@@ -1123,7 +1532,7 @@ impl PaneMemory {
                     pointer += len as u64;
                 };
 
-                bytes[i % len].push(*byte); // cycling the columns, incrementing the depth each lines
+                bytes[i % len].push((pointer - len as u64 + (i % len) as u64, *byte)); // cycling the columns, incrementing the depth each lines
                 if i == 40*len - 1 { // 40 lines (more cant fit on the screen)
                     break;
                 }
@@ -1142,12 +1551,14 @@ impl PaneMemory {
             );
 
             let byte_columns: iced::widget::Row<'_, Message> = row(bytes.iter().map(|col| column( // we create columns seperately for nicer alignment
-                col.iter().map(|byte| text(
+                col.iter().map(|(address, byte)| mouse_area(text(
                     self.format.form(*byte) // formatting the bytes
                 ).size(size - 5)
                 .height(size)
                 .center()
-                .style(style::widget_text)
+                .style(if self.watch.is_some_and(|(watch, ..)| watch == *address) || self.edit.as_ref().is_some_and(|edit| edit.address == *address) {style::widget_text_toggled} else {style::widget_text}))
+                .on_press(Message::Pane(PaneMessage::MemoryEdit(id, *address, *byte))) // left click edits the memory from the byte
+                .on_right_press(Message::Pane(PaneMessage::MemoryWatch(id, *address))) // right click sets up a watchpoint on the byte
                 .into())
             ).align_x(iced::Alignment::Center)
             .width(match self.format {
@@ -1170,10 +1581,60 @@ impl PaneMemory {
             ).on_scroll(move |delta| Message::Pane(PaneMessage::MemoryAddress(id, delta, -3)))) // same message, bigger increment and negatives
         };
 
+        let small = 24;
+        let watch = self.watch.map(|(address, watch_size, kind)| row![ // the watchpoint being set up
+            text(format!("Watch 0x{:x}", address)).size(small - 10).center().height(small),
+            widget_fill(),
+            pick_list([1u8, 2, 4, 8], Some(watch_size), move |watch_size| Message::Pane(PaneMessage::MemoryWatchSize(id, watch_size)))
+            .text_size(small - 10),
+            pick_list([WatchKind::Write, WatchKind::ReadWrite, WatchKind::Execute], Some(kind), move |kind| Message::Pane(PaneMessage::MemoryWatchKind(id, kind)))
+            .text_size(small - 10),
+            button(text("Watch").center().size(small - 10).style(style::widget_text))
+            .height(small)
+            .style(style::widget_button)
+            .on_press_maybe(if state.internal.stopped {Some(Message::Pane(PaneMessage::MemoryWatchAdd(id)))} else {None}), // debug registers can be set only while stopped
+            button(text("Cancel").center().size(small - 10).style(style::widget_text))
+            .height(small)
+            .style(style::widget_button)
+            .on_press(Message::Pane(PaneMessage::MemoryWatchCancel(id)))
+        ].spacing(5).padding(3));
+
+        let writable = PID.access().is_some() && CORE.access().is_none();
+        let edit = self.edit.as_ref().map(|edit| row![ // the value being written
+            text(format!("Write 0x{:x}", edit.address)).size(small - 10).center().height(small),
+            pick_list(WriteKind::ALL, Some(edit.kind), move |kind| Message::Pane(PaneMessage::MemoryEditKind(id, kind)))
+            .text_size(small - 10),
+            text_input(edit.kind.placeholder(&self.format), &edit.value)
+            .on_input(move |data| Message::Pane(PaneMessage::MemoryEditInput(id, data)))
+            .on_paste(move |data| Message::Pane(PaneMessage::MemoryEditInput(id, data)))
+            .on_submit_maybe(if writable {Some(Message::Pane(PaneMessage::MemoryEditWrite(id)))} else {None})
+            .size(small - 10)
+            .line_height(iced::Pixels(small as f32 - 8.))
+            .width(Length::Fill),
+            button(text("Write").center().size(small - 10).style(style::widget_text))
+            .height(small)
+            .style(style::widget_button)
+            .on_press_maybe(if writable {Some(Message::Pane(PaneMessage::MemoryEditWrite(id)))} else {None}), // a core dump is read-only
+            button(text("Cancel").center().size(small - 10).style(style::widget_text))
+            .height(small)
+            .style(style::widget_button)
+            .on_press(Message::Pane(PaneMessage::MemoryEditCancel(id)))
+        ].spacing(5).padding(3));
+
+        let watchpoints = WATCHPOINTS.access().clone().unwrap_or_default();
+        let watchpoints = column(watchpoints.into_iter().enumerate().filter_map(|(i, watchpoint)| watchpoint.map(|watchpoint| row![ // active watchpoints
+            text(format!("{}: {} ({} B, {})", i, watchpoint.label(), watchpoint.size, watchpoint.kind)).size(small - 10).center().height(small),
+            widget_fill(),
+            button(text("Remove").center().size(small - 10).style(style::widget_text))
+            .height(small)
+            .style(style::widget_button)
+            .on_press_maybe(if state.internal.stopped {Some(Message::Operation(Operation::WatchpointRemove(i)))} else {None})
+        ].spacing(5).padding(padding::left(3).right(3)).into())));
+
         let content = container(column![
             field,
-            memory
-        ]).style(style::back);
+            watchpoints
+        ].push_maybe(watch).push_maybe(edit).push(memory)).style(style::back);
         content
     }
 }
@@ -1184,6 +1645,9 @@ pub struct PaneCode {
     pub file: Option<String>,
     update: bool,
     breakpoints: Vec<Option<u64>>,
+    editor: Option<Box<BreakpointEditor>>, // boxed, the code panes are the largest ones
+    function: String, // function breakpoint field
+    completions: Vec<String>, // fuzzy matches of the function field
     scrollable: scrollable::Id,
     viewport: Option<scrollable::Viewport>
 }
@@ -1230,7 +1694,7 @@ impl PaneCode {
             Some(file) => {
                 let comp_path = PathBuf::from(self.dir.as_ref().unwrap());
                 let file_path = PathBuf::from(file);
-                let code = self.code_display(comp_path, file_path, source, &state.internal.pane.file); // err if not contents
+                let code = self.code_display(id, comp_path, file_path, source, &state.internal.pane.file); // err if not contents
                 if code.is_ok() {
                     container(scrollable(
                     code.unwrap()
@@ -1248,20 +1712,105 @@ impl PaneCode {
             program_message("Directory not selected.").into()
         };
 
+        let editor = self.editor.as_ref().map(|editor| { // right click in the gutter opens the breakpoint settings
+            let toggle = |name: &'a str, toggled: bool, message: fn(pane_grid::Pane) -> PaneMessage| button(
+                text(name).center().size(size - 14)
+                .style(if toggled {style::widget_text_toggled} else {style::widget_text})
+            ).height(size - 6)
+            .style(if toggled {style::widget_button_toggled} else {style::widget_button})
+            .on_press(Message::Pane(message(id)));
+
+            let action = |name: &'a str, message: fn(pane_grid::Pane) -> PaneMessage| button(
+                text(name).center().size(size - 14).style(style::widget_text)
+            ).height(size - 6)
+            .style(style::widget_button)
+            .on_press_maybe(if state.internal.stopped {Some(Message::Pane(message(id)))} else {None}); // the tracee has to be stopped
+
+            column![
+                row![
+                    text(format!("Line at 0x{:x}", anti_normal(editor.address))).size(size - 12).center().height(size),
+                    widget_fill(),
+                    action("Run to here", PaneMessage::CodeRunTo),
+                    action("Jump here", PaneMessage::CodeJump)
+                ].spacing(5).height(size),
+                row![
+                    text("Condition:").size(size - 12).center().height(size).width(80),
+                    text_input("i == 42 && p->len > 3", &editor.condition)
+                    .on_input(move |data| Message::Pane(PaneMessage::CodeBreakpointCondition(id, data)))
+                    .on_paste(move |data| Message::Pane(PaneMessage::CodeBreakpointCondition(id, data)))
+                    .on_submit(Message::Pane(PaneMessage::CodeBreakpointApply(id)))
+                    .size(size - 12)
+                    .line_height(iced::Pixels(size as f32 - 10.))
+                    .width(Length::Fill)
+                ].spacing(5),
+                row![
+                    text("Log:").size(size - 12).center().height(size).width(80),
+                    text_input("i={i} buf={buf} (prints without stopping)", editor.settings.log.as_deref().unwrap_or(""))
+                    .on_input(move |data| Message::Pane(PaneMessage::CodeBreakpointLog(id, data)))
+                    .on_paste(move |data| Message::Pane(PaneMessage::CodeBreakpointLog(id, data)))
+                    .on_submit(Message::Pane(PaneMessage::CodeBreakpointApply(id)))
+                    .size(size - 12)
+                    .line_height(iced::Pixels(size as f32 - 10.))
+                    .width(Length::Fill)
+                ].spacing(5),
+                row![
+                    text("Ignore hits:").size(size - 12).center().height(size).width(80),
+                    text_input("0", &editor.ignore)
+                    .on_input(move |data| Message::Pane(PaneMessage::CodeBreakpointIgnore(id, data)))
+                    .on_paste(move |data| Message::Pane(PaneMessage::CodeBreakpointIgnore(id, data)))
+                    .on_submit(Message::Pane(PaneMessage::CodeBreakpointApply(id)))
+                    .size(size - 12)
+                    .line_height(iced::Pixels(size as f32 - 10.))
+                    .width(60),
+                    toggle("Enabled", editor.settings.enabled, PaneMessage::CodeBreakpointToggleEnabled),
+                    toggle("Temporary", editor.settings.temporary, PaneMessage::CodeBreakpointToggleTemporary),
+                    widget_fill(),
+                    button(text("Apply").center().size(size - 14).style(style::widget_text))
+                    .height(size - 6)
+                    .style(style::widget_button)
+                    .on_press(Message::Pane(PaneMessage::CodeBreakpointApply(id))),
+                    button(text("Cancel").center().size(size - 14).style(style::widget_text))
+                    .height(size - 6)
+                    .style(style::widget_button)
+                    .on_press(Message::Pane(PaneMessage::CodeBreakpointCancel(id)))
+                ].spacing(5).height(size)
+            ].spacing(3).padding(3)
+        });
+
+        let function = text_input("Function...", &self.function) // breakpoint by the function name
+        .on_input(move |data| Message::Pane(PaneMessage::CodeFunctionInput(id, data)))
+        .on_paste(move |data| Message::Pane(PaneMessage::CodeFunctionInput(id, data)))
+        .on_submit(Message::Pane(PaneMessage::CodeFunctionBreakpoint(id, self.completions.first().cloned().unwrap_or(self.function.clone())))) // the best match
+        .size(size - 12)
+        .line_height(iced::Pixels(size as f32 - 10.))
+        .width(200);
+
+        let completions = if self.completions.is_empty() {None} else {Some(
+            column(self.completions.iter().map(|name| button(text(name.clone()).size(size - 14).style(style::widget_text))
+                .width(Length::Fill)
+                .padding(padding::left(5))
+                .style(style::widget_button)
+                .on_press(Message::Pane(PaneMessage::CodeFunctionBreakpoint(id, name.clone())))
+                .into()
+            )).padding(3)
+        )};
+
         container(
             column![
                 row![
                     hash_list,
                     file_list,
+                    function,
                     widget_fill(),
                     update_button
-                ].spacing(10).padding(3).height(size+6),
-                code
-            ]
+                ].spacing(10).padding(3).height(size+6)
+            ].push_maybe(completions)
+            .push_maybe(editor)
+            .push(code)
         ).style(style::back)
     }
 
-    fn code_display<'a>(&self, comp_path: PathBuf, file_path: PathBuf, source: SourceMap, line: &Option<SourceIndex>) -> Result<Row<'a, Message>, ()> {
+    fn code_display<'a>(&self, id: pane_grid::Pane, comp_path: PathBuf, file_path: PathBuf, source: SourceMap, line: &Option<SourceIndex>) -> Result<Row<'a, Message>, ()> {
 
         let (file, _index) = match source.get_file(comp_path.clone(), file_path.clone()) {
             Some(file) => file,
@@ -1277,10 +1826,24 @@ impl PaneCode {
         let breakpoints = column(
             self.breakpoints.iter().enumerate().map(|(index, address)| {
                 lines.push(index);
-                breakpoint_button(*address, size).into()
+                match address {
+                    Some(address) => mouse_area(breakpoint_button(Some(*address), size))
+                    .on_right_press(Message::Pane(PaneMessage::CodeBreakpointEdit(id, *address)))
+                    .into(),
+                    None => breakpoint_button(None, size).into()
+                }
             })
         );
 
+        let bind = BREAKPOINTS.access();
+        let counts = column( // hits of the breakpoints (and the hit it stops at, when ignoring)
+            self.breakpoints.iter().map(|address| {
+                let count = address.and_then(|address| bind.as_ref().unwrap().get(&address)).map(hit_count).unwrap_or_default();
+                text(count).style(style::weak).size(size-12).height(size).center().into()
+            })
+        ).width(Length::Shrink).align_x(iced::Right);
+        drop(bind);
+
         let highlight = match line { // getting the current line, if we are in the correct source file
             Some(index) => {
                 let real_name = &source.index_with_line(index).path;
@@ -1308,12 +1871,20 @@ impl PaneCode {
         .wrapping(text::Wrapping::None);
 
         Ok(row![
-            breakpoints, line_number, container("").width(5), text
+            breakpoints, counts, line_number, container("").width(5), text
         ].spacing(0)
         .padding(5))
     }
 
 }
+#[derive(Debug, Clone)]
+struct BreakpointEditor { // settings of a breakpoint being edited in the code pane, applied all at once
+    address: u64,
+    condition: String,
+    ignore: String,
+    settings: Breakpoint
+}
+
 impl Default for PaneCode {
     fn default() -> Self {
         Self {
@@ -1321,6 +1892,9 @@ impl Default for PaneCode {
             dir: None,
             file: None,
             breakpoints: Vec::new(),
+            editor: None,
+            function: String::new(),
+            completions: Vec::new(),
             scrollable: scrollable::Id::unique(),
             viewport: None
         }
@@ -1376,8 +1950,31 @@ impl PaneInfo {
         field, value
     ].padding(5).spacing(30);
 
+    let mut bases: Vec<(String, u64)> = Vec::new(); // the start of the first mapping of every file, the bias of the pie and the libraries
+    let running = MAPS.access().is_some();
+    for map in MAPS.access().iter().flatten().filter(|map| map.offset == 0 && map.name.starts_with('/')) {
+        if !bases.iter().any(|(name, _)| *name == map.name) {
+            bases.push((map.name.clone(), map.range.start));
+        }
+    }
+    let shift = match *EXEC_SHIFT.access() {
+        Some(shift) => format!("0x{:x}", shift),
+        None if !running => String::from("Not running"),
+        None => String::from("0x0 (not relocated)")
+    };
+
+    let loads = column![
+        row![
+            text("Executable shift:").size(size-5).center().height(size),
+            text(shift).size(size-5).center().height(size).style(style::widget_text)
+        ].spacing(30)
+    ].extend(bases.into_iter().map(|(name, base)| row![
+        text(format!("0x{:x}", base)).size(size-5).center().height(size).style(style::widget_text).font(font::Font::MONOSPACE),
+        text(name).size(size-5).center().height(size).wrapping(text::Wrapping::None)
+    ].spacing(15).into())).padding(5);
+
     container(
-        data
+        scrollable(column![data, loads]).direction(scrollable::Direction::Both {vertical: scrollbar(), horizontal: scrollbar()}).width(Length::Fill).height(Length::Fill)
     ).width(Length::Fill).height(Length::Fill).style(style::back)
 }
 }
@@ -1523,18 +2120,20 @@ impl PaneStack {
 
         for (i, open) in open_vec.iter().enumerate() {
             if !open {continue;} // skipping the hidden ones
-            let (depth, line) = &stack[i];
+            let (depth, line, watchpoint) = &stack[i];
             let data = if *depth == 0 { // funtion lines
                 text(line).style(style::widget_text)
             } else {
                 text(line)
             }.height(size).size(size-5);
-            lines = lines.push(
-                container(data)
-                .padding(padding::left(size*depth.checked_sub(1).unwrap_or(0) as u16)) // removing the indent on the closing brackets of params, while keeping correct collapse rules
-            );
+            let data = container(data)
+            .padding(padding::left(size*depth.checked_sub(1).unwrap_or(0) as u16)); // removing the indent on the closing brackets of params, while keeping correct collapse rules
+            lines = match watchpoint { // right click on a variable in memory watches it
+                Some(watchpoint) => lines.push(mouse_area(data).on_right_press(Message::Operation(Operation::WatchpointAdd(watchpoint.clone())))),
+                None => lines.push(data)
+            };
             match stack.get(i+1) {
-                Some((next_depth, _)) => if next_depth > depth { // if next line has greater indent, we generate a collapse button
+                Some((next_depth, ..)) => if next_depth > depth { // if next line has greater indent, we generate a collapse button
                     collapse = collapse.push(Self::collapse_button(open_vec[i+1], i, size, id));
                 } else {
                     collapse = collapse.push(container("").height(size));
@@ -1570,7 +2169,7 @@ pub struct PaneAssembly {
 }
 impl PaneAssembly {
     fn view<'a>(&self, state: &'a State, _id: pane_grid::Pane) -> Container<'a, Message> {
-        if PID.access().is_none() {
+        if PID.access().is_none() && CORE.access().is_none() {
             return program_message("Start the program to display assembly instructions.");
         }
 
@@ -1629,6 +2228,249 @@ impl Default for PaneAssembly {
 }
 
 
+struct PaneThreads;
+impl PaneThreads {
+    fn view<'a>(state: &'a State) -> Container<'a, Message> {
+        let bind = THREADS.access();
+        let threads = match bind.as_ref() {
+            Some(threads) => threads,
+            None => return program_message("Start the program to display threads.")
+        };
+        let selected = *THREAD.access();
+        let inferiors = INFERIORS.access().as_ref().is_some_and(|inferiors| inferiors.len() > 1);
+        let core = CORE.access().is_some();
+        let size = 25;
+
+        let lines = column(threads.iter().map(|(tid, thread)| {
+            let current = Some(*tid) == selected;
+            let location = match (&thread.function, thread.regs) {
+                _ if thread.running => String::from("running"),
+                (Some(function), Some(regs)) => format!("{} (0x{:x})", function, regs.rip),
+                (None, Some(regs)) => format!("0x{:x}", regs.rip),
+                _ => String::new()
+            };
+            let line = row![
+                text(if inferiors {format!("{}/{}", thread.process, tid)} else {format!("{}", tid)}).size(size - 10).width(if inferiors {110} else {70}) // process/thread when following forks
+                .style(if current {style::line} else {style::widget_text}).font(if current {BOLD} else {font::Font::DEFAULT}),
+                text(thread.name.clone()).size(size - 10).width(130),
+                text(location).size(size - 10).style(style::weak)
+            ].spacing(10);
+
+            button(line.height(size - 8))
+            .style(if current {style::widget_button_toggled} else {style::widget_button})
+            .on_press_maybe(if (state.internal.stopped || core) && !current {Some(Message::Operation(Operation::SelectThread(*tid)))} else {None}) // switching is possible only while stopped
+            .width(Length::Fill)
+            .into()
+        })).spacing(2).padding(padding::Padding {right: 10., ..Default::default()});
+
+        container(
+            scrollable(lines).height(Length::Fill).width(Length::Fill)
+        ).padding(5).style(style::back)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PaneSyscalls {
+    catch: String // the catchpoint being typed, "name" or "name fd=N"
+}
+impl PaneSyscalls {
+    fn view<'a>(&'a self, state: &'a State, id: pane_grid::Pane) -> Container<'a, Message> {
+        let tracing = SYSCALLS.access().clone().unwrap_or_default();
+        let size = 25;
+
+        let catch = text_input("Catch syscall (openat, write fd=2)...", &self.catch)
+        .on_input(move |data| Message::Pane(PaneMessage::SyscallsCatchInput(id, data)))
+        .on_paste(move |data| Message::Pane(PaneMessage::SyscallsCatchInput(id, data)))
+        .on_submit(Message::Pane(PaneMessage::SyscallsCatch(id)))
+        .size(size - 10)
+        .line_height(iced::Pixels(size as f32 - 8.))
+        .width(Length::Fill);
+
+        let controls = row![
+            button(text("Log").center().size(size - 10).style(if tracing.log {style::widget_text_toggled} else {style::widget_text}))
+            .height(size)
+            .style(if tracing.log {style::widget_button_toggled} else {style::widget_button})
+            .on_press(Message::Operation(Operation::SyscallLog(!tracing.log))),
+            button(text("Clear").center().size(size - 10).style(style::widget_text))
+            .height(size)
+            .style(style::widget_button)
+            .on_press(Message::Operation(Operation::SyscallClear)),
+            catch
+        ].spacing(5).padding(3);
+
+        let catchpoints = column(tracing.catchpoints.iter().enumerate().map(|(i, catchpoint)| row![
+            text(format!("{}: {}", i, catchpoint)).size(size - 10).center().height(size),
+            widget_fill(),
+            button(text("Remove").center().size(size - 10).style(style::widget_text))
+            .height(size)
+            .style(style::widget_button)
+            .on_press(Message::Operation(Operation::CatchpointRemove(i)))
+        ].spacing(5).padding(padding::left(3).right(3)).into()));
+
+        let log = &state.internal.pane.syscalls;
+        let lines = column(log[log.len().saturating_sub(SYSCALLS_SHOWN)..].iter().map(|entry| // only the newest, the log can be long
+            text(entry.to_string()).size(size - 11).font(font::Font::MONOSPACE).into()
+        )).padding(padding::Padding {right: 10., ..Default::default()});
+
+        let content = if log.is_empty() {
+            program_message(if tracing.log {"No syscalls logged yet."} else {"Toggle Log to record the syscalls of the program."})
+        } else {
+            container(scrollable(lines).anchor_bottom().height(Length::Fill).width(Length::Fill))
+        };
+
+        container(column![
+            controls,
+            catchpoints,
+            content.padding(5)
+        ]).style(style::back)
+    }
+}
+
+struct PaneLog;
+impl PaneLog {
+    fn view(state: &State) -> Container<'_, Message> {
+        let size = 25;
+
+        let controls = row![
+            text(format!("{} messages", state.internal.pane.logs.len())).size(size - 10).center().height(size),
+            widget_fill(),
+            button(text("Clear").center().size(size - 10).style(style::widget_text))
+            .height(size)
+            .style(style::widget_button)
+            .on_press(Message::Operation(Operation::LogClear))
+        ].spacing(5).padding(3).height(size + 6);
+
+        let logs = &state.internal.pane.logs;
+        let lines = column(logs[logs.len().saturating_sub(LOGS_SHOWN)..].iter().map(|entry| // only the newest
+            text(entry.to_string()).size(size - 11).font(font::Font::MONOSPACE).into()
+        )).padding(padding::Padding {right: 10., ..Default::default()});
+
+        let content = if logs.is_empty() {
+            program_message("Set a log message on a breakpoint (right click its line) to print here without stopping.")
+        } else {
+            container(scrollable(lines).anchor_bottom().height(Length::Fill).width(Length::Fill))
+        };
+
+        container(column![
+            controls,
+            content.padding(5)
+        ]).style(style::back)
+    }
+}
+
+struct PaneBreakpoints;
+impl PaneBreakpoints {
+    fn view<'a>() -> Container<'a, Message> {
+        let size = 25;
+
+        let mut breakpoints: Vec<(u64, Breakpoint)> = BREAKPOINTS.access().iter().flatten().map(|(address, breakpoint)| (*address, breakpoint.clone())).collect();
+        breakpoints.sort_by_key(|(address, _)| *address);
+
+        let lines_bind = LINES.access();
+        let source_bind = SOURCE.access();
+        let lines = column(breakpoints.into_iter().map(|(address, breakpoint)| {
+            let index = lines_bind.as_ref().and_then(|lines| lines.get(&address));
+            let location = match (index, source_bind.as_ref()) {
+                (Some(index), Some(source)) => format!("{}:{}", source.index_with_line(index).path.display(), index.line),
+                _ => String::from("no source")
+            };
+            let function = breakpoint.function.clone().or_else(|| function_name(anti_normal(address))).unwrap_or_default();
+            let real = if image_loaded(address) {format!("0x{:x}", anti_normal(address))} else {String::from("pending")}; // the library is not loaded yet
+
+            let mut details = Vec::new();
+            if let Some(condition) = &breakpoint.condition {
+                details.push(format!("if {}", condition));
+            }
+            if let Some(log) = &breakpoint.log {
+                details.push(format!("log \"{}\"", log));
+            }
+            if breakpoint.temporary {
+                details.push(String::from("temporary"));
+            }
+            let hits = hit_count(&breakpoint);
+
+            let mut toggled = breakpoint.clone(); // only the settings are applied by the edit
+            toggled.enabled ^= true;
+            let weak = if breakpoint.enabled {style::widget_text} else {style::weak};
+
+            row![
+                column![
+                    row![
+                        text(location).size(size - 10).style(weak).font(BOLD),
+                        text(function).size(size - 10).style(weak),
+                        text(real).size(size - 11).font(font::Font::MONOSPACE).style(style::weak),
+                    ].spacing(10),
+                    text(format!("{} hits {}", if hits.is_empty() {"0"} else {&hits}, details.join(", "))).size(size - 12).style(style::weak)
+                ].width(Length::Fill),
+                button(text("Show").center().size(size - 10).style(style::widget_text))
+                .height(size)
+                .style(style::widget_button)
+                .on_press_maybe(index.map(|_| Message::Pane(PaneMessage::BreakpointsShow(address)))), // only with a source line
+                button(text(if breakpoint.enabled {"Disable"} else {"Enable"}).center().size(size - 10).style(style::widget_text))
+                .height(size)
+                .style(style::widget_button)
+                .on_press(Message::Operation(Operation::BreakpointEdit(address, toggled))),
+                button(text("Delete").center().size(size - 10).style(style::widget_text))
+                .height(size)
+                .style(style::widget_button)
+                .on_press(Message::Operation(Operation::BreakpointRemove(address)))
+            ].spacing(5).align_y(iced::Alignment::Center).into()
+        })).spacing(4).padding(padding::Padding {right: 10., ..Default::default()});
+
+        let content = if BREAKPOINTS.access().as_ref().is_none_or(|breakpoints| breakpoints.is_empty()) {
+            program_message("Click the gutter of the Code or Assembly pane to add a breakpoint.")
+        } else {
+            container(scrollable(lines).height(Length::Fill).width(Length::Fill))
+        };
+
+        container(content.padding(5)).style(style::back)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PaneWatch {
+    input: String // the expression being typed
+}
+impl PaneWatch {
+    fn view<'a>(&'a self, state: &'a State, id: pane_grid::Pane) -> Container<'a, Message> {
+        let size = 25;
+
+        let input = row![
+            text_input("Watch expression (v.len, vec_len(&v))...", &self.input)
+            .on_input(move |data| Message::Pane(PaneMessage::WatchInput(id, data)))
+            .on_paste(move |data| Message::Pane(PaneMessage::WatchInput(id, data)))
+            .on_submit(Message::Pane(PaneMessage::WatchAdd(id)))
+            .size(size - 10)
+            .line_height(iced::Pixels(size as f32 - 8.))
+            .width(Length::Fill),
+            button(text("Add").center().size(size - 10).style(style::widget_text))
+            .height(size)
+            .style(style::widget_button)
+            .on_press(Message::Pane(PaneMessage::WatchAdd(id)))
+        ].spacing(5).padding(3);
+
+        let watches = &state.internal.pane.watches;
+        let lines = column(watches.iter().enumerate().map(|(i, (expression, value))| row![
+            text(format!("{} = {}", expression, value)).size(size - 11).font(font::Font::MONOSPACE).width(Length::Fill),
+            button(text("Remove").center().size(size - 10).style(style::widget_text))
+            .height(size)
+            .style(style::widget_button)
+            .on_press(Message::Operation(Operation::WatchRemove(i)))
+        ].spacing(5).align_y(iced::Alignment::Center).into())).spacing(2).padding(padding::Padding {right: 10., ..Default::default()});
+
+        let content = if watches.is_empty() {
+            program_message("Add an expression to evaluate it on every stop.")
+        } else {
+            container(scrollable(lines).height(Length::Fill).width(Length::Fill))
+        };
+
+        container(column![
+            input,
+            content.padding(5)
+        ]).style(style::back)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LayoutMessage { // Messages regarding the PaneGrid
     SidebarLeftToggle,
@@ -1643,8 +2485,26 @@ pub enum LayoutMessage { // Messages regarding the PaneGrid
 pub enum PaneMessage { // Messages regarding the Panes themselves
     // Control
     ControlSelectSignal(pane_grid::Pane, Signal),
+    ControlLaunchArgs(pane_grid::Pane, String),
+    ControlLaunchEnv(pane_grid::Pane, String),
+    ControlLaunchDirectory(pane_grid::Pane, String),
+    ControlLaunchClearEnv,
+    ControlLaunchNoAslr,
+    ControlLaunchFollowFork(config::FollowFork),
+    ControlLaunchStopAt(config::StopAt),
+    ControlRefreshProcesses(pane_grid::Pane),
+    ControlSelectProcess(pane_grid::Pane, object::Process),
+    ControlSelectDisposition(pane_grid::Pane, Signal),
+    ControlDisposition(Signal, config::Disposition),
     // Registers
     RegistersChangeFormat(pane_grid::Pane, Base),
+    RegistersEdit(pane_grid::Pane, usize),
+    RegistersEditInput(pane_grid::Pane, String),
+    RegistersEditApply(pane_grid::Pane),
+    RegistersEditCancel(pane_grid::Pane),
+    RegistersFlag(u64),
+    RegistersVector(pane_grid::Pane),
+    RegistersLane(pane_grid::Pane, Lane),
     // Memory
     MemoryChangeFormat(pane_grid::Pane, ByteBase),
     MemoryToggleSize(pane_grid::Pane),
@@ -1653,6 +2513,17 @@ pub enum PaneMessage { // Messages regarding the Panes themselves
     MemoryPaste(pane_grid::Pane, String),
     MemoryAddress(pane_grid::Pane, iced::mouse::ScrollDelta, i8), // the i8 is as a signed multiplier (eg. scroll by how much per scroll)
     MemoryReset(pane_grid::Pane),
+    MemoryWatch(pane_grid::Pane, u64),
+    MemoryWatchSize(pane_grid::Pane, u8),
+    MemoryWatchKind(pane_grid::Pane, WatchKind),
+    MemoryWatchAdd(pane_grid::Pane),
+    MemoryWatchCancel(pane_grid::Pane),
+    MemoryEdit(pane_grid::Pane, u64, u8),
+    MemoryEditKind(pane_grid::Pane, WriteKind),
+    MemoryEditInput(pane_grid::Pane, String),
+    MemoryEditWrite(pane_grid::Pane),
+    MemoryEditCancel(pane_grid::Pane),
+    MemoryReload(pane_grid::Pane),
     // Code
     CodeSelectDir(pane_grid::Pane, String),
     CodeSelectFile(pane_grid::Pane, String),
@@ -1660,6 +2531,18 @@ pub enum PaneMessage { // Messages regarding the Panes themselves
     CodeBreakpoints(pane_grid::Pane, Vec<Option<u64>>),
     CodeToggleUpdate(pane_grid::Pane),
     CodeScroll(pane_grid::Pane, scrollable::Viewport),
+    CodeBreakpointEdit(pane_grid::Pane, u64),
+    CodeBreakpointCondition(pane_grid::Pane, String),
+    CodeBreakpointLog(pane_grid::Pane, String),
+    CodeBreakpointIgnore(pane_grid::Pane, String),
+    CodeBreakpointToggleEnabled(pane_grid::Pane),
+    CodeBreakpointToggleTemporary(pane_grid::Pane),
+    CodeBreakpointApply(pane_grid::Pane),
+    CodeBreakpointCancel(pane_grid::Pane),
+    CodeRunTo(pane_grid::Pane),
+    CodeFunctionInput(pane_grid::Pane, String),
+    CodeFunctionBreakpoint(pane_grid::Pane, String),
+    CodeJump(pane_grid::Pane),
     // Terminal
     TerminalType(pane_grid::Pane, String),
     TerminalPaste(pane_grid::Pane, String),
@@ -1668,6 +2551,14 @@ pub enum PaneMessage { // Messages regarding the Panes themselves
     StackUpdate(pane_grid::Pane),
     StackCollapse(pane_grid::Pane, usize),
     StackExpand(pane_grid::Pane, usize),
+    // Syscalls
+    SyscallsCatchInput(pane_grid::Pane, String),
+    SyscallsCatch(pane_grid::Pane),
+    // Breakpoints
+    BreakpointsShow(u64),
+    // Watch
+    WatchInput(pane_grid::Pane, String),
+    WatchAdd(pane_grid::Pane),
     // Assembly
     AssemblyUpdate(Result<(crate::dwarf::Assembly, usize), ()>),
 }
@@ -1765,9 +2656,11 @@ fn statusbar<'a>(state: &State, height: u16) -> Container<'a, Message> {
     };
     content = content.push(delimiter(10));
 
-    content = match PID.access().as_ref() { // pid
-        None => status_text("Program not running".to_string(), content, size, default),
-        Some(pid) => status_text(format!("Pid: {}", pid), content, size, Some(style::widget_text)),
+    let core = CORE.access().as_ref().map(|core| (core.pid, core.signal));
+    content = match (PID.access().as_ref(), core) { // pid
+        (Some(pid), _) => status_text(format!("Pid: {}", pid), content, size, Some(style::widget_text)),
+        (None, Some((pid, _))) => status_text(format!("Core of pid: {}", pid), content, size, Some(style::widget_text)),
+        (None, None) => status_text("Program not running".to_string(), content, size, default)
     };
 
     if PID.access().is_some() { // if pid, display the current state of the tracee
@@ -1777,6 +2670,7 @@ fn statusbar<'a>(state: &State, height: u16) -> Container<'a, Message> {
             let mut msg = match state.status.unwrap() {
                 nix::sys::wait::WaitStatus::Signaled(_, signal, _) => format!("Stopped: {signal}"),
                 nix::sys::wait::WaitStatus::Stopped(_, signal) => format!("Stopped: {signal}"),
+                nix::sys::wait::WaitStatus::PtraceEvent(_, _, event) if event == nix::sys::ptrace::Event::PTRACE_EVENT_EXEC as i32 => "Stopped: exec".to_string(),
                 _ => "Stopped".to_string()
             };
 
@@ -1787,25 +2681,52 @@ fn statusbar<'a>(state: &State, height: u16) -> Container<'a, Message> {
             if state.internal.manual {
                 msg = "Stopped".to_string()
             }
-            content = status_text(msg, content, size, default);
 
-            match &state.internal.pane.file {
-                Some(index) => {
-                    let bind = SOURCE.access();
-                    let source = bind.as_ref().unwrap();
-                    let file = source.index_with_line(index).path.to_str().unwrap();
-                    let msg = format!("At line: {} in file {}", index.line, file);
+            if let Some(message) = &state.internal.watchpoint {
+                msg = message.clone()
+            }
+
+            if let Some(message) = &state.internal.returned {
+                msg = message.clone()
+            }
 
-                    content = content.push(widget_fill());
-                    content = status_text(msg, content, size, default);
-                }
-                None => ()
-            };
+            if let Some(message) = &state.internal.catchpoint {
+                msg = message.clone()
+            }
+
+            if let Some(message) = &state.internal.reversed {
+                msg = message.clone()
+            }
+            content = status_text(msg, content, size, default);
         } else {
             content = status_text("Running...".to_string(), content, size, default);
         };
     }
 
+    if (PID.access().is_some() || core.is_some()) && let Some(shift) = *EXEC_SHIFT.access() { // the load address of the executable, to compare the addresses between runs
+        content = content.push(delimiter(10));
+        content = status_text(format!("Base: 0x{:x}", shift), content, size, default);
+    }
+
+    if let Some((_, signal)) = core { // the core dump is shown like a stopped program
+        content = content.push(delimiter(10));
+        let msg = match signal {
+            Some(signal) => format!("Crashed: {signal}"),
+            None => "Crashed".to_string()
+        };
+        content = status_text(msg, content, size, Some(style::error));
+    }
+
+    if ((PID.access().is_some() && state.internal.stopped) || core.is_some()) && let Some(index) = &state.internal.pane.file {
+        let bind = SOURCE.access();
+        let source = bind.as_ref().unwrap();
+        let file = source.index_with_line(index).path.to_str().unwrap();
+        let msg = format!("At line: {} in file {}", index.line, file);
+
+        content = content.push(widget_fill());
+        content = status_text(msg, content, size, default);
+    }
+
     match state.status {
         Some(nix::sys::wait::WaitStatus::Exited(pid, ecode)) => { // if no pid but exited status, display exit code
             content = content.push(delimiter(10));
@@ -1845,13 +2766,18 @@ fn main_frame<'a>(state: &'a State) -> Container<'a, Message> {
 fn pane_view<'a>(id: pane_grid::Pane, pane: &'a Pane, state: &'a State) -> pane_grid::Content<'a, Message> { // selecting the view function of each pane
     let (content, titlebar) = match pane {
         Pane::Control(control) => (control.view(state, id), pane_titlebar("Control", "icons/pane_control.svg")),
-        Pane::Registers(registers) => (registers.view(id), pane_titlebar("Registers", "icons/pane_registers.svg")),
-        Pane::Memory(memory) => (memory.view(id), pane_titlebar("Memory", "icons/pane_memory.svg")),
+        Pane::Registers(registers) => (registers.view(state, id), pane_titlebar("Registers", "icons/pane_registers.svg")),
+        Pane::Memory(memory) => (memory.view(state, id), pane_titlebar("Memory", "icons/pane_memory.svg")),
         Pane::Code(code) => (code.view(state, id), pane_titlebar("Code", "icons/pane_source.svg")),
         Pane::Info => (PaneInfo::view(), pane_titlebar("ELF Info", "icons/pane_info.svg")),
         Pane::Terminal(terminal) => (terminal.view(state, id), pane_titlebar("Terminal", "icons/pane_terminal.svg")),
         Pane::Stack(stack) => (stack.view(state, id), pane_titlebar("CallStack", "icons/pane_stack.svg")),
         Pane::Assembly(assembly) => (assembly.view(state, id), pane_titlebar("Assembly", "icons/pane_assembly.svg")),
+        Pane::Threads => (PaneThreads::view(state), pane_titlebar("Threads", "icons/pane_threads.svg")),
+        Pane::Syscalls(syscalls) => (syscalls.view(state, id), pane_titlebar("Syscalls", "icons/pane_syscalls.svg")),
+        Pane::Watch(watch) => (watch.view(state, id), pane_titlebar("Watch", "icons/pane_watch.svg")),
+        Pane::Log => (PaneLog::view(state), pane_titlebar("Log", "icons/pane_log.svg")),
+        Pane::Breakpoints => (PaneBreakpoints::view(), pane_titlebar("Breakpoints", "icons/pane_breakpoints.svg")),
 
         _ => (container(text("Some other pane")), pane_grid::TitleBar::new(text("UNDEFINED")))
     };
@@ -1991,8 +2917,82 @@ pub fn pane_message<'a>(state: &'a mut State, message: PaneMessage, task: &mut O
     match message {
         // Control
         PaneMessage::ControlSelectSignal(pane, signal) => get_pane(panes, pane).control().selected_signal = Some(signal),
+        PaneMessage::ControlLaunchArgs(pane, data) => { // every edit is parsed and saved into the launch config right away
+            LAUNCH.access().as_mut().unwrap().args = Some(split_arguments(&data));
+            get_pane(panes, pane).control().args = data;
+        },
+        PaneMessage::ControlLaunchEnv(pane, data) => {
+            LAUNCH.access().as_mut().unwrap().env = Some(split_arguments(&data));
+            get_pane(panes, pane).control().env = data;
+        },
+        PaneMessage::ControlLaunchDirectory(pane, data) => {
+            LAUNCH.access().as_mut().unwrap().directory = if data.trim().is_empty() {None} else {Some(data.trim().to_string())};
+            get_pane(panes, pane).control().directory = data;
+        },
+        PaneMessage::ControlLaunchClearEnv => {
+            let mut bind = LAUNCH.access();
+            let launch = bind.as_mut().unwrap();
+            launch.clear_env = Some(!launch.clear_env.unwrap_or(false));
+        },
+        PaneMessage::ControlLaunchNoAslr => {
+            let mut bind = LAUNCH.access();
+            let launch = bind.as_mut().unwrap();
+            launch.no_aslr = Some(!launch.no_aslr.unwrap_or(false));
+        },
+        PaneMessage::ControlLaunchFollowFork(follow) => LAUNCH.access().as_mut().unwrap().follow_fork = Some(follow), // used on the next fork, even while running
+        PaneMessage::ControlLaunchStopAt(stop) => LAUNCH.access().as_mut().unwrap().stop_at = Some(stop),
+        PaneMessage::ControlSelectDisposition(pane, signal) => get_pane(panes, pane).control().disposition_signal = Some(signal),
+        PaneMessage::ControlDisposition(signal, disposition) => { // used from the next signal, even while running
+            CONFIG.access().as_mut().unwrap().signals.get_or_insert_default().insert(signal.as_str().to_string(), disposition);
+            if let Err(err) = config::save_signals() {
+                Dialog::warning(&format!("Could not save the signal table into the config: {}", err), Some("Config Error"));
+            }
+        },
+        PaneMessage::ControlRefreshProcesses(pane) => {
+            let control = get_pane(panes, pane).control();
+            control.processes = object::list_processes();
+            if !control.processes.iter().any(|process| Some(process) == control.selected_process.as_ref()) { // the selected process has exited since
+                control.selected_process = None;
+            }
+        },
+        PaneMessage::ControlSelectProcess(pane, process) => get_pane(panes, pane).control().selected_process = Some(process),
         // Registers
         PaneMessage::RegistersChangeFormat(pane, base) => get_pane(panes, pane).registers().format = base,
+        PaneMessage::RegistersEdit(pane, index) => {
+            let data = get_pane(panes, pane).registers();
+            let value = REGISTERS.access().map(|mut regs| data.format.form(*register_fields(&mut regs)[index].1)).unwrap_or_default();
+            data.edit = Some((index, value));
+        },
+        PaneMessage::RegistersEditInput(pane, input) => if let Some((_, value)) = &mut get_pane(panes, pane).registers().edit {
+            *value = input;
+        },
+        PaneMessage::RegistersEditApply(pane) => {
+            let data = get_pane(panes, pane).registers();
+            let (index, value) = match &data.edit {
+                Some(edit) => edit.clone(),
+                None => return
+            };
+            let mut regs = match *REGISTERS.access() {
+                Some(regs) => regs,
+                None => return
+            };
+            match parse_register(&value) {
+                Some(value) => *register_fields(&mut regs)[index].1 = value,
+                None => {Dialog::error(&format!("{} is not a number (prefix 0x, 0o or 0b for the other bases).", value.trim()), Some("Register Error")); return;}
+            };
+            data.edit = None;
+            *task = Some(Task::done(Message::Operation(Operation::RegistersEdit(regs))));
+        },
+        PaneMessage::RegistersEditCancel(pane) => get_pane(panes, pane).registers().edit = None,
+        PaneMessage::RegistersFlag(bit) => if let Some(mut regs) = *REGISTERS.access() {
+            regs.eflags ^= 1 << bit;
+            *task = Some(Task::done(Message::Operation(Operation::RegistersEdit(regs))));
+        },
+        PaneMessage::RegistersVector(pane) => {
+            let data = get_pane(panes, pane).registers();
+            data.vector = !data.vector;
+        },
+        PaneMessage::RegistersLane(pane, lane) => get_pane(panes, pane).registers().lane = lane,
         // Code
         PaneMessage::CodeSelectDir(pane, dir) => { // setting the directory and reseting the file (unless the same one has been selected)
             let data = get_pane(panes, pane).code();
@@ -2069,6 +3069,84 @@ pub fn pane_message<'a>(state: &'a mut State, message: PaneMessage, task: &mut O
             }
         },
         PaneMessage::CodeScroll(pane, view) => get_pane(panes, pane).code().viewport = Some(view),
+        PaneMessage::CodeBreakpointEdit(pane, address) => { // the line doesnt need a breakpoint yet, it gets created on apply
+            let settings = BREAKPOINTS.access().as_ref().unwrap().get(&address).cloned().unwrap_or_default();
+            get_pane(panes, pane).code().editor = Some(Box::new(BreakpointEditor {
+                address,
+                condition: settings.condition.clone().unwrap_or_default(),
+                ignore: settings.ignore.to_string(),
+                settings
+            }));
+        },
+        PaneMessage::CodeBreakpointCondition(pane, data) => if let Some(editor) = &mut get_pane(panes, pane).code().editor {
+            editor.condition = data;
+        },
+        PaneMessage::CodeBreakpointLog(pane, data) => if let Some(editor) = &mut get_pane(panes, pane).code().editor {
+            editor.settings.log = Some(data); // the message is kept in the settings, trimmed on apply
+        },
+        PaneMessage::CodeBreakpointIgnore(pane, data) => if let Some(editor) = &mut get_pane(panes, pane).code().editor {
+            editor.ignore = data;
+        },
+        PaneMessage::CodeBreakpointToggleEnabled(pane) => if let Some(editor) = &mut get_pane(panes, pane).code().editor {
+            editor.settings.enabled ^= true;
+        },
+        PaneMessage::CodeBreakpointToggleTemporary(pane) => if let Some(editor) = &mut get_pane(panes, pane).code().editor {
+            editor.settings.temporary ^= true;
+        },
+        PaneMessage::CodeBreakpointApply(pane) => { // empty condition removes it, invalid settings keep the editor open
+            let data = get_pane(panes, pane).code();
+            let mut editor = match data.editor.clone() {
+                Some(editor) => editor,
+                None => return
+            };
+            let condition = editor.condition.trim().to_string();
+            if !condition.is_empty() && let Err(err) = eval::parse(&condition) {
+                Dialog::error(&format!("Invalid condition: {}", err), Some("Breakpoint Error"));
+                return;
+            }
+            let log = editor.settings.log.as_deref().unwrap_or("").trim().to_string();
+            if !log.is_empty() && let Err(err) = eval::parse_message(&log) {
+                Dialog::error(&format!("Invalid log message: {}", err), Some("Breakpoint Error"));
+                return;
+            }
+            editor.settings.ignore = match editor.ignore.trim() {
+                "" => 0,
+                ignore => match ignore.parse() {
+                    Ok(ignore) => ignore,
+                    Err(_) => {Dialog::error("The ignore count has to be a positive number.", Some("Breakpoint Error")); return;}
+                }
+            };
+            editor.settings.condition = if condition.is_empty() {None} else {Some(condition)};
+            editor.settings.log = if log.is_empty() {None} else {Some(log)};
+            data.editor = None;
+            *task = Some(Task::done(Message::Operation(Operation::BreakpointEdit(editor.address, editor.settings))));
+        },
+        PaneMessage::CodeBreakpointCancel(pane) => get_pane(panes, pane).code().editor = None,
+        PaneMessage::CodeFunctionInput(pane, data) => {
+            let data_pane = get_pane(panes, pane).code();
+            data_pane.completions = if data.trim().is_empty() {Vec::new()} else {crate::dwarf::function_matches(&data, 8)};
+            data_pane.function = data;
+        },
+        PaneMessage::CodeFunctionBreakpoint(pane, name) => { // adds the breakpoint and shows it in the pane
+            let found = crate::dwarf::function_breakpoints(name.trim());
+            let index = match found.first() { // the pane shows the first one, the breakpoint is set on all of them
+                Some((_, index)) => index.clone(),
+                None => {Dialog::error(&format!("Function {} not found.", name.trim()), Some("Breakpoint Error")); return;}
+            };
+            let addresses = found.into_iter().map(|(address, _)| address).collect();
+            let data = get_pane(panes, pane).code();
+            data.function = String::new();
+            data.completions = Vec::new();
+            data.update = false; // so we stay on the function
+            let (scroll, load) = code_update(pane, &index, data);
+            *task = Some(Task::done(Message::Operation(Operation::BreakpointFunction(addresses, name.trim().to_string()))).chain(load).chain(scroll));
+        },
+        PaneMessage::CodeRunTo(pane) => if let Some(editor) = get_pane(panes, pane).code().editor.take() {
+            *task = Some(Task::done(Message::Operation(Operation::RunTo(editor.address))));
+        },
+        PaneMessage::CodeJump(pane) => if let Some(editor) = get_pane(panes, pane).code().editor.take() {
+            *task = Some(Task::done(Message::Operation(Operation::Jump(editor.address))));
+        },
         // Memory
         PaneMessage::MemoryChangeFormat(pane, base) => get_pane(panes, pane).memory().format = base,
         PaneMessage::MemoryToggleSize(pane) => get_pane(panes, pane).memory().more_bytes ^= true,
@@ -2139,6 +3217,53 @@ pub fn pane_message<'a>(state: &'a mut State, message: PaneMessage, task: &mut O
             data.incorrect = false; // reset the NaN error
             update_memory(data);
         },
+        PaneMessage::MemoryWatch(pane, address) => get_pane(panes, pane).memory().watch = Some((address, 1, WatchKind::Write)),
+        PaneMessage::MemoryWatchSize(pane, size) => if let Some(watch) = &mut get_pane(panes, pane).memory().watch {
+            watch.1 = size;
+        },
+        PaneMessage::MemoryWatchKind(pane, kind) => if let Some(watch) = &mut get_pane(panes, pane).memory().watch {
+            watch.2 = kind;
+        },
+        PaneMessage::MemoryWatchAdd(pane) => if let Some((address, size, kind)) = get_pane(panes, pane).memory().watch.take() {
+            *task = Some(Task::done(Message::Operation(Operation::WatchpointAdd(Watchpoint::new(address, size, kind, None)))));
+        },
+        PaneMessage::MemoryWatchCancel(pane) => get_pane(panes, pane).memory().watch = None,
+        PaneMessage::MemoryEdit(pane, address, byte) => {
+            let data = get_pane(panes, pane).memory();
+            let value = match data.format {
+                ByteBase::Chr if byte.is_ascii_graphic() || byte == b' ' => (byte as char).to_string(),
+                ByteBase::Chr => String::new(),
+                _ => data.format.form(byte)
+            };
+            data.edit = Some(MemoryEdit {address, kind: WriteKind::Byte, value});
+        },
+        PaneMessage::MemoryEditKind(pane, kind) => if let Some(edit) = &mut get_pane(panes, pane).memory().edit {
+            edit.kind = kind;
+        },
+        PaneMessage::MemoryEditInput(pane, input) => if let Some(edit) = &mut get_pane(panes, pane).memory().edit {
+            edit.value = input;
+        },
+        PaneMessage::MemoryEditWrite(pane) => {
+            let data = get_pane(panes, pane).memory();
+            let edit = match &data.edit {
+                Some(edit) => edit,
+                None => return
+            };
+            match edit.kind.encode(&edit.value, &data.format) {
+                Ok(bytes) => {
+                    *task = Some(Task::done(Message::Operation(Operation::MemoryWrite(edit.address, bytes))).chain(Task::done(Message::Pane(PaneMessage::MemoryReload(pane)))));
+                    data.edit = None;
+                },
+                Err(err) => Dialog::error(&err, Some("Memory Error"))
+            }
+        },
+        PaneMessage::MemoryEditCancel(pane) => get_pane(panes, pane).memory().edit = None,
+        PaneMessage::MemoryReload(pane) => { // after a write, the same bytes are read again
+            let data = get_pane(panes, pane).memory();
+            if let Ok(bytes) = read_memory(data.read_address, data.data.len()) {
+                data.data = bytes;
+            }
+        },
         PaneMessage::MemoryReset(pane) => {
             let data = get_pane(panes, pane).memory(); // we get the beginning of the memory (from tge memory maps)
             let mut beginning = 0;
@@ -2162,6 +3287,48 @@ pub fn pane_message<'a>(state: &'a mut State, message: PaneMessage, task: &mut O
             data.incorrect = false; // reset the NaN error
             update_memory(data); // we trigger update memory
         },
+        // Syscalls
+        PaneMessage::SyscallsCatchInput(pane, data) => get_pane(panes, pane).syscalls().catch = data,
+        PaneMessage::SyscallsCatch(pane) => {
+            let data = get_pane(panes, pane).syscalls();
+            match crate::syscall::Catchpoint::parse(&data.catch) {
+                Ok(catchpoint) => {
+                    data.catch.clear();
+                    *task = Some(Task::done(Message::Operation(Operation::CatchpointAdd(catchpoint))));
+                },
+                Err(err) => Dialog::error(&err, Some("Catchpoint Error"))
+            }
+        },
+        // Breakpoints
+        PaneMessage::BreakpointsShow(address) => { // the code panes show the line of the breakpoint and stay on it
+            let index = match LINES.access().as_ref().and_then(|lines| lines.get(&address).cloned()) {
+                Some(index) => index,
+                None => return
+            };
+            let mut scroll_tasks = Vec::new();
+            let mut load_tasks = Vec::new();
+            for (id, pane) in panes.iter_mut() {
+                if let Pane::Code(data) = pane {
+                    data.update = false;
+                    let (scroll, load) = code_update(*id, &index, data);
+                    scroll_tasks.push(scroll);
+                    load_tasks.push(load);
+                }
+            }
+            *task = Some(Task::batch(load_tasks).chain(Task::batch(scroll_tasks)));
+        },
+        // Watch
+        PaneMessage::WatchInput(pane, data) => get_pane(panes, pane).watch().input = data,
+        PaneMessage::WatchAdd(pane) => {
+            let data = get_pane(panes, pane).watch();
+            match crate::eval::parse(&data.input) { // the syntax is checked right away, the value on every stop
+                Ok(_) => {
+                    *task = Some(Task::done(Message::Operation(Operation::WatchAdd(data.input.trim().to_string()))));
+                    data.input.clear();
+                },
+                Err(err) => Dialog::error(&err, Some("Watch Error"))
+            }
+        },
         // Terminal
         PaneMessage::TerminalType(pane, data) => get_pane(panes, pane).terminal().input = data,
         PaneMessage::TerminalPaste(pane, data) => get_pane(panes, pane).terminal().input = data,
@@ -2192,7 +3359,7 @@ pub fn pane_message<'a>(state: &'a mut State, message: PaneMessage, task: &mut O
 
             let stack = state.internal.pane.stack.as_ref().unwrap();
             let mut first = true;
-            let mut open_new: Vec<bool> = stack.iter().rev().map(|(depth, _)| { // this maps all of the function lines to be shown, and the first function to be expanded
+            let mut open_new: Vec<bool> = stack.iter().rev().map(|(depth, ..)| { // this maps all of the function lines to be shown, and the first function to be expanded
                 if first {
                     if *depth == 0 {
                         first = false;
@@ -2230,6 +3397,17 @@ pub fn source_content(file: PathBuf) -> Option<String> { // reading and processi
     }
 }
 
+pub fn reload_launch(state: &mut State) { // the launch fields of the Control panes, after the session changed the launch config
+    let launch = LAUNCH.access().clone().unwrap();
+    for (_, pane) in state.layout.panes.iter_mut() {
+        if let Pane::Control(control) = pane {
+            control.args = join_arguments(&launch.args.clone().unwrap_or_default());
+            control.env = join_arguments(&launch.env.clone().unwrap_or_default());
+            control.directory = launch.directory.clone().unwrap_or_default();
+        }
+    }
+}
+
 pub fn create_breakpoints(comp_path: PathBuf, index: usize, len: usize) -> Vec<Option<u64>> { // creates breakpoints each time the file is selected, to save performance
     let bind = LINES.access();
     let lines = bind.as_ref().unwrap();
@@ -2294,10 +3472,10 @@ pub fn update_memory(pane: &mut PaneMemory) { // Function Handling the bytes loa
     pane.data = data.unwrap(); // setting the data
 }
 
-fn stack_open(stack: &Vec<(usize, String)>, pane: &mut PaneStack, line: usize, open: bool) { // we expand or collapse the lines until we get to the same level again
+fn stack_open(stack: &[StackLine], pane: &mut PaneStack, line: usize, open: bool) { // we expand or collapse the lines until we get to the same level again
     let upper = stack[line].0;
     let open_vec = &mut pane.open;
-    for (i, (depth, _)) in stack.iter().skip(line+1).enumerate() { // skipping the first lines
+    for (i, (depth, ..)) in stack.iter().skip(line+1).enumerate() { // skipping the first lines
         if *depth == upper {break;}
         open_vec[i+line+1] = open;
     };
@@ -2472,6 +3650,139 @@ impl ByteBase {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WriteKind { // how the value typed in the Memory pane is turned into bytes
+    Byte, // in the display format of the pane
+    U8, U16, U32, U64,
+    I8, I16, I32, I64,
+    F32, F64,
+    Str // with the null byte at the end
+}
+impl WriteKind {
+    const ALL: [WriteKind; 12] = [Self::Byte, Self::U8, Self::U16, Self::U32, Self::U64, Self::I8, Self::I16, Self::I32, Self::I64, Self::F32, Self::F64, Self::Str];
+
+    fn placeholder(&self, format: &ByteBase) -> &'static str {
+        match (self, format) {
+            (Self::Byte, ByteBase::Hex) => "ff",
+            (Self::Byte, ByteBase::Dec) => "255",
+            (Self::Byte, ByteBase::Chr) => "A",
+            (Self::F32 | Self::F64, _) => "3.14",
+            (Self::Str, _) => "text",
+            _ => "42 or 0x2a"
+        }
+    }
+
+    fn encode(&self, value: &str, format: &ByteBase) -> Result<Vec<u8>, String> { // little endian bytes of the value
+        fn unsigned(value: &str, max: u64) -> Result<u64, String> {
+            let value = value.trim();
+            let number = match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => value.parse()
+            };
+            match number {
+                Ok(number) if number <= max => Ok(number),
+                Ok(_) => Err(format!("{} does not fit into the type.", value)),
+                Err(_) => Err(format!("{} is not a positive number.", value))
+            }
+        }
+        fn signed(value: &str, min: i64, max: i64) -> Result<i64, String> {
+            match value.trim().parse::<i64>() {
+                Ok(number) if (min..=max).contains(&number) => Ok(number),
+                Ok(_) => Err(format!("{} does not fit into the type.", value.trim())),
+                Err(_) => Err(format!("{} is not a number.", value.trim()))
+            }
+        }
+        fn float(value: &str) -> Result<f64, String> {
+            value.trim().parse().map_err(|_| format!("{} is not a floating point number.", value.trim()))
+        }
+
+        Ok(match self {
+            Self::Byte => vec![match format {
+                ByteBase::Hex => u8::from_str_radix(value.trim().trim_start_matches("0x"), 16).map_err(|_| format!("{} is not a hexadecimal byte.", value.trim()))?,
+                ByteBase::Dec => value.trim().parse().map_err(|_| format!("{} is not a byte (0-255).", value.trim()))?,
+                ByteBase::Chr => match value.as_bytes() {
+                    [byte] => *byte,
+                    _ => return Err(String::from("Enter a single ASCII character."))
+                }
+            }],
+            Self::U8 => vec![unsigned(value, u8::MAX as u64)? as u8],
+            Self::U16 => (unsigned(value, u16::MAX as u64)? as u16).to_le_bytes().to_vec(),
+            Self::U32 => (unsigned(value, u32::MAX as u64)? as u32).to_le_bytes().to_vec(),
+            Self::U64 => unsigned(value, u64::MAX)?.to_le_bytes().to_vec(),
+            Self::I8 => (signed(value, i8::MIN as i64, i8::MAX as i64)? as i8).to_le_bytes().to_vec(),
+            Self::I16 => (signed(value, i16::MIN as i64, i16::MAX as i64)? as i16).to_le_bytes().to_vec(),
+            Self::I32 => (signed(value, i32::MIN as i64, i32::MAX as i64)? as i32).to_le_bytes().to_vec(),
+            Self::I64 => signed(value, i64::MIN, i64::MAX)?.to_le_bytes().to_vec(),
+            Self::F32 => (float(value)? as f32).to_le_bytes().to_vec(),
+            Self::F64 => float(value)?.to_le_bytes().to_vec(),
+            Self::Str => {
+                let mut bytes = value.as_bytes().to_vec();
+                bytes.push(0);
+                bytes
+            }
+        })
+    }
+}
+impl std::fmt::Display for WriteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Self::Byte => "byte",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::Str => "string"
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Lane { // the elements of a vector register, lowest first
+    I8, I16, I32, I64,
+    #[default]
+    F32,
+    F64
+}
+impl Lane {
+    const ALL: [Lane; 6] = [Self::I8, Self::I16, Self::I32, Self::I64, Self::F32, Self::F64];
+
+    fn form(&self, bytes: &[u8]) -> String {
+        let size = match self {
+            Self::I8 => 1,
+            Self::I16 => 2,
+            Self::I32 | Self::F32 => 4,
+            Self::I64 | Self::F64 => 8
+        };
+        let lanes = bytes.chunks_exact(size).map(|lane| match self {
+            Self::I8 => (lane[0] as i8).to_string(),
+            Self::I16 => i16::from_le_bytes(lane.try_into().unwrap()).to_string(),
+            Self::I32 => i32::from_le_bytes(lane.try_into().unwrap()).to_string(),
+            Self::I64 => i64::from_le_bytes(lane.try_into().unwrap()).to_string(),
+            Self::F32 => f32::from_le_bytes(lane.try_into().unwrap()).to_string(),
+            Self::F64 => f64::from_le_bytes(lane.try_into().unwrap()).to_string()
+        }).collect::<Vec<String>>();
+        format!("{{{}}}", lanes.join(", "))
+    }
+}
+impl std::fmt::Display for Lane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::F32 => "f32",
+            Self::F64 => "f64"
+        })
+    }
+}
+
 // Widgets helpers
 
 fn scrollbar() -> scrollable::Scrollbar {
@@ -2495,10 +3806,18 @@ fn svg_button<'a>(icon: &str, size: u16, svg_style: Option<fn(&Theme, svg::Statu
 fn breakpoint_button<'a>(address: Option<u64>, size: u16) -> button::Button<'a, Message> { // if address in the breakpoints, then toggled
     match address {
         Some(address) => {
-            let present = BREAKPOINTS.access().as_ref().unwrap().contains_key(&address);
+            let breakpoint = BREAKPOINTS.access().as_ref().unwrap().get(&address).cloned();
+            let present = breakpoint.is_some();
             button(
                 svg(Handle::from_memory(Asset::get("icons/signal.svg").unwrap().data))
-                .style(if present {style::breakpoint_svg_toggled} else {style::breakpoint_svg})
+                .style(match breakpoint {
+                    Some(breakpoint) if !breakpoint.enabled => style::breakpoint_svg_disabled,
+                    Some(breakpoint) if breakpoint.log.is_some() => style::breakpoint_svg_log,
+                    Some(breakpoint) if breakpoint.condition.is_some() => style::breakpoint_svg_conditional,
+                    Some(breakpoint) if breakpoint.temporary => style::breakpoint_svg_temporary,
+                    Some(_) => style::breakpoint_svg_toggled,
+                    None => style::breakpoint_svg
+                })
                 .width(Length::Fill)
                 .height(Length::Fill)
             ).style(style::breakpoint)
@@ -2514,6 +3833,14 @@ fn breakpoint_button<'a>(address: Option<u64>, size: u16) -> button::Button<'a,
     }
 }
 
+fn hit_count(breakpoint: &Breakpoint) -> String { // "hits", or "hits/stop" while ignoring
+    match (breakpoint.hits, breakpoint.ignore) {
+        (0, 0) => String::new(),
+        (hits, 0) => hits.to_string(),
+        (hits, ignore) => format!("{}/{}", hits, ignore + 1)
+    }
+}
+
 fn widget_fill<'a>() -> Container<'a, Message> {
     container("").width(Length::Fill).height(Length::Fill)
 }
@@ -2529,10 +3856,77 @@ fn program_message<'a>(msg: &'a str) -> Container<'a, Message> { // When the ui
     .style(style::back)
 }
 
+fn split_arguments(line: &str) -> Vec<String> { // splits the input like a shell would (whitespace, with "double" or 'single' quotes and \ escapes)
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut started = false; // so "" produces an empty argument
+    let mut chars = line.chars();
+
+    while let Some(char) = chars.next() {
+        match (char, quote) {
+            ('\\', Some('\'')) => current.push(char), // no escapes inside single quotes
+            ('\\', _) => if let Some(next) = chars.next() {current.push(next)},
+            (c, Some(q)) if c == q => quote = None,
+            (c, Some(_)) => current.push(c),
+            ('"' | '\'', None) => {quote = Some(char); started = true},
+            (c, None) if c.is_whitespace() => if started || !current.is_empty() {
+                args.push(std::mem::take(&mut current));
+                started = false;
+            },
+            (c, None) => current.push(c)
+        }
+    }
+    if started || !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+fn join_arguments(args: &[String]) -> String { // inverse of split_arguments, for displaying the config values
+    args.iter().map(|arg| if arg.is_empty() || arg.contains(char::is_whitespace) || arg.contains(['"', '\'', '\\']) {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    } else {
+        arg.clone()
+    }).collect::<Vec<String>>().join(" ")
+}
+
 pub fn process_string(file: &mut String) { // iced default font cannot display every character (even ones like tabs and such), therefore we replace them
     *file = file.chars().map(|char| match char {
         '\n' => "\n".to_string(),
         '\t' => "    ".to_string(),
         _ => if char.is_ascii_control() {"".to_string()} else {char.to_string()}
     }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(split_arguments(r#""a b" c\"d"#), args(&["a b", "c\"d"]));
+        assert_eq!(split_arguments("  one\ttwo   three "), args(&["one", "two", "three"]));
+        assert_eq!(split_arguments(r#"'a "b" \n' "x 'y'""#), args(&["a \"b\" \\n", "x 'y'"])); // no escapes inside single quotes
+        assert_eq!(split_arguments(r#"a\ b "" ''"#), args(&["a b", "", ""]));
+        assert_eq!(split_arguments(r#"pre"fix"'ed'"#), args(&["prefixed"]));
+        assert_eq!(split_arguments(""), args(&[]));
+    }
+
+    #[test]
+    fn round_trip() {
+        for list in [
+            args(&["a b", "c\"d"]),
+            args(&["it's", "", "back\\slash", "tab\there", "plain"]),
+            args(&["'", "''", "\"", "\\", "a'b\"c\\d e"]),
+            args(&["--config", "test.toml", "ünïcode arg"])
+        ] {
+            assert_eq!(split_arguments(&join_arguments(&list)), list);
+        }
+        assert_eq!(join_arguments(&args(&["plain", "a b", "it's"])), r#"plain 'a b' 'it'\''s'"#);
+    }
 }
\ No newline at end of file
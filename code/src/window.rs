@@ -36,9 +36,19 @@ pub struct Internal {
     pub no_debug: bool,
     pub static_exec: bool,
     pub stopped: bool,
+    pub attached: bool, // we attached to a running process (detaching instead of killing)
+    pub detach: bool, // detach on the next stop
+    pub stopped_thread: Option<nix::unistd::Pid>, // the thread that caused the last stop (the last signal belongs to it)
     pub breakpoint: bool,
+    pub watchpoint: Option<String>, // the last stop was caused by a watchpoint (the message with the values)
+    pub returned: Option<String>, // the last stop was the end of a finish (the message with the return value)
+    pub catchpoint: Option<String>, // the last stop was caused by a syscall catchpoint (the message with the call)
     pub manual: bool,
     pub source_step: Option<trace::Breakpoints>,
+    pub step_scope: Option<trace::StepScope>, // the source step is a next or a finish
+    pub record_steps: Option<usize>, // a recorded source step, made of single steps (their count)
+    pub reversed: Option<String>, // the last stop was a reverse step (the message with the history left)
+    pub pending_breakpoints: Vec<crate::session::SessionBreakpoint>, // the breakpoints of the session not found in the file (yet), saved with the session again
     pub pane: PaneData
 }
 
@@ -48,8 +58,12 @@ pub struct PaneData {
     pub comp_dir: Option<std::path::PathBuf>,
     pub output: String,
     pub assembly: Option<crate::dwarf::Assembly>,
-    pub stack: Option<Vec<(usize, String)>>,
-    pub unique_stack: u32
+    pub stack: Option<Vec<crate::dwarf::StackLine>>,
+    pub unique_stack: u32,
+    pub syscalls: Vec<crate::syscall::Entry>, // the log of the Syscalls pane
+    pub logs: Vec<crate::trace::LogEntry>, // the messages of the logpoints, in the Log pane
+    pub watches: Vec<(String, String)>, // the expressions of the Watch pane, with their values from the last stop
+    pub signals: Vec<String> // the printed signals (their disposition), shown in the Control pane
 }
 
 // MESSAGES OF THE APP
@@ -59,15 +73,31 @@ pub enum Message {
     Layout(ui::LayoutMessage),
     Pane(ui::PaneMessage),
     Operation(trace::Operation),
+    Close, // the window close button, the session is saved before exiting
     None
 }
 
+fn session_changed(message: &Message) -> bool { // the messages that change what the session of the executable saves, the rest is saved when the file is unloaded or the window closed
+    matches!(message,
+        Message::Operation(
+            trace::Operation::BreakpointAdd(_) | trace::Operation::BreakpointFunction(..) | trace::Operation::BreakpointRemove(_) | trace::Operation::BreakpointEdit(..) |
+            trace::Operation::WatchAdd(_) | trace::Operation::WatchRemove(_)
+        ) |
+        Message::Layout(ui::LayoutMessage::SidebarLeftToggle | ui::LayoutMessage::SidebarRightToggle | ui::LayoutMessage::PanelToggle | ui::LayoutMessage::Drag(_)) |
+        Message::Pane(
+            ui::PaneMessage::ControlLaunchArgs(..) | ui::PaneMessage::ControlLaunchEnv(..) | ui::PaneMessage::ControlLaunchDirectory(..) |
+            ui::PaneMessage::ControlLaunchClearEnv | ui::PaneMessage::ControlLaunchNoAslr | ui::PaneMessage::ControlLaunchFollowFork(_) | ui::PaneMessage::ControlLaunchStopAt(_)
+        )
+    )
+}
+
 // MAIN APP RUNTIME LOOP
 
 pub fn run_app() -> iced::Result {
     application("Three Body Debugger", App::update, App::view)
     .theme(App::theme)
     .window(App::default().settings)
+    .subscription(App::subscription)
     .run_with(|| (App::default(), Task::none()))
 }
 
@@ -90,6 +120,7 @@ impl App {
                 size,
                 position,
                 min_size: Some(iced::Size { width: 400., height: 400. }),
+                exit_on_close_request: false,
                 ..Default::default()
             }
         }
@@ -98,12 +129,20 @@ impl App {
     fn update(&mut self, message: Message) -> Task<Message> {
         let mut task = None;
         let state = &mut self.state;
+        let session = session_changed(&message);
         match message {
             Message::Operation(operation) => trace::operation_message(state, operation, &mut task),
             Message::Layout(layout) => ui::layout_message(state, layout),
             Message::Pane(pane) => ui::pane_message(state, pane, &mut task),
+            Message::Close => {
+                crate::session::save(state);
+                return iced::exit();
+            },
             _ => ()
         };
+        if session {
+            crate::session::save(state);
+        }
 
         task.unwrap_or(Task::none())
     }
@@ -117,6 +156,10 @@ impl App {
     fn theme(&self) -> Theme {
         self.theme.clone()
     }
+
+    fn subscription(&self) -> iced::Subscription<Message> {
+        window::close_requests().map(|_| Message::Close)
+    }
 }
 
 
@@ -170,4 +213,11 @@ impl Dialog {
         .set_title("Select an executable to debug.")
         .pick_file()
     }
+
+    pub fn core_file() -> Option<std::path::PathBuf> { // to select a core dump of the executable
+        rfd::FileDialog::new()
+        .set_directory(std::env::current_dir().unwrap_or("/".into()))
+        .set_title("Select a core dump to debug.")
+        .pick_file()
+    }
 }
\ No newline at end of file